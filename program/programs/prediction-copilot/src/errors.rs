@@ -34,4 +34,157 @@ pub enum ErrorCode {
     
     #[msg("Signal timestamp too old")]
     SignalTooOld,
+    
+    #[msg("Price must be greater than zero")]
+    InvalidPrice,
+    
+    #[msg("Duration must be greater than zero")]
+    InvalidDuration,
+    
+    #[msg("No pending admin transfer")]
+    NoPendingAdmin,
+    
+    #[msg("Invalid role")]
+    InvalidRole,
+    
+    #[msg("Unknown pause flag bits")]
+    InvalidPauseFlags,
+    
+    #[msg("Invalid multisig signer set")]
+    InvalidMultisigSigners,
+    
+    #[msg("Invalid multisig threshold")]
+    InvalidThreshold,
+    
+    #[msg("Signer is not a multisig member")]
+    NotMultisigSigner,
+    
+    #[msg("Proposal has not reached its approval threshold")]
+    ProposalNotApproved,
+    
+    #[msg("Proposal already executed")]
+    ProposalAlreadyExecuted,
+    
+    #[msg("Proposal action does not match instruction")]
+    ProposalActionMismatch,
+    
+    #[msg("Price increases, shorter periods and removed features must be queued through the timelock")]
+    PriceIncreaseRequiresTimelock,
+    
+    #[msg("Timelock eta is earlier than the minimum delay")]
+    TimelockTooShort,
+    
+    #[msg("Timelock has not elapsed")]
+    TimelockNotElapsed,
+    
+    #[msg("Payment mint is not accepted")]
+    UnsupportedPaymentMint,
+    
+    #[msg("Payment mint is already accepted")]
+    PaymentMintAlreadyAccepted,
+    
+    #[msg("Payment mint table is full")]
+    TooManyPaymentMints,
+    
+    #[msg("Cannot remove the last payment mint")]
+    CannotRemoveLastPaymentMint,
+    
+    #[msg("Revenue split shares must sum to 10000 bps")]
+    InvalidRevenueSplit,
+    
+    #[msg("Beneficiary token account missing or invalid")]
+    InvalidBeneficiaryAccount,
+    
+    #[msg("Vault is empty")]
+    NothingToDistribute,
+    
+    #[msg("Active subscription is on another tier, use change_tier")]
+    UseChangeTier,
+    
+    #[msg("Invalid refund policy")]
+    InvalidRefundPolicy,
+    
+    #[msg("Subscription is not yet inside the renewal window")]
+    RenewalTooEarly,
+    
+    #[msg("Auto-renew allowance is missing or too low")]
+    RenewalAllowanceTooLow,
+    
+    #[msg("Free trials are disabled")]
+    TrialsDisabled,
+    
+    #[msg("Trial is only available without an active subscription")]
+    TrialUnavailable,
+    
+    #[msg("Invalid promo code parameters")]
+    InvalidPromoCode,
+    
+    #[msg("Promo code is inactive, expired, exhausted or not valid for this tier")]
+    PromoCodeUnavailable,
+    
+    #[msg("Promo code already redeemed by this wallet")]
+    PromoCodeAlreadyRedeemed,
+    
+    #[msg("Invalid referral commission")]
+    InvalidReferralCommission,
+    
+    #[msg("Referrer is missing, invalid or not bound to this subscription")]
+    InvalidReferrer,
+    
+    #[msg("Invalid seat count")]
+    InvalidSeatCount,
+    
+    #[msg("All paid seats are assigned")]
+    NoSeatsAvailable,
+    
+    #[msg("Oracle price is invalid or its confidence interval is too wide")]
+    PriceTooUncertain,
+    
+    #[msg("Payment exceeds the maximum amount allowed by the caller")]
+    SlippageExceeded,
+    
+    #[msg("Mints with a transfer fee are not accepted for payment")]
+    TransferFeeMintUnsupported,
+    
+    #[msg("Pass token is missing or not held by this wallet")]
+    InvalidPass,
+    
+    #[msg("Pass has not expired yet")]
+    PassNotExpired,
+    
+    #[msg("Tier is retired")]
+    TierInactive,
+    
+    #[msg("Invalid price lock policy")]
+    InvalidPriceLockPolicy,
+    
+    #[msg("Mints with a transfer hook or permanent delegate are not accepted for payment")]
+    MintExtensionUnsupported,
+    
+    #[msg("Running periods were paid in a different mint")]
+    PaymentMintMismatch,
+    
+    #[msg("Too many prepaid periods")]
+    TooManyPaidPeriods,
+    
+    #[msg("Trials cannot be cancelled for a refund")]
+    TrialNotRefundable,
+    
+    #[msg("Subscription lapsed too long ago to be renewed by the crank")]
+    RenewalLapsed,
+    
+    #[msg("Auto-renewal account must be passed to cancel")]
+    AutoRenewalRequired,
+    
+    #[msg("Pass is for another tier than the active subscription")]
+    PassTierMismatch,
+    
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    
+    #[msg("Vault only holds money still owed as refunds")]
+    NothingDistributable,
+    
+    #[msg("Gifts cannot go to the payer's own subscription")]
+    GiftToSelf,
 }
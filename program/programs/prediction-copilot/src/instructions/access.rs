@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::state::*;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct VerifySubscription<'info> {
+    /// CHECK: Wallet whose access is being checked, does not need to sign
+    pub user: UncheckedAccount<'info>,
+    
+    /// Config of the tier `user` is expected to hold, the source of `features`
+    #[account(
+        seeds = [b"tier", &[tier_config.tier_id]],
+        bump = tier_config.bump
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    #[account(
+        seeds = [b"subscription", user.key().as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Option<Account<'info, Subscription>>,
+    
+    /// Org seat held by `user`, must be passed together with `org`
+    #[account(
+        seeds = [b"seat", seat.org.as_ref(), user.key().as_ref()],
+        bump = seat.bump
+    )]
+    pub seat: Option<Account<'info, OrgSeat>>,
+    
+    #[account(
+        seeds = [b"org", org.owner.as_ref()],
+        bump = org.bump
+    )]
+    pub org: Option<Account<'info, OrgSubscription>>,
+    
+    /// Subscription pass, must be passed together with `pass_token`
+    #[account(
+        seeds = [b"pass", pass.mint.as_ref()],
+        bump = pass.bump
+    )]
+    pub pass: Option<Account<'info, SubscriptionPass>>,
+    
+    /// Token account holding the pass, access follows whoever owns it
+    pub pass_token: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SubscriptionStatus {
+    pub tier: u8,
+    pub expires_at: i64,
+    pub features: u32,              // TierConfig.features of `tier`
+}
+
+/// Returns `user`'s access to `tier_config`'s tier through return data. Any
+/// active subscription, seat or pass on that tier grants it, the one running
+/// longest is reported.
+pub fn verify_subscription(ctx: Context<VerifySubscription>) -> Result<SubscriptionStatus> {
+    let now = Clock::get()?.unix_timestamp;
+    let tier_config = &ctx.accounts.tier_config;
+    // (tier, expires_at) of every active source
+    let mut active: Vec<(u8, i64)> = Vec::with_capacity(3);
+    
+    if let Some(subscription) = &ctx.accounts.subscription {
+        if let Some(tier) = subscription.active_tier(now) {
+            active.push((tier, subscription.expires_at));
+        }
+    }
+    
+    match (&ctx.accounts.seat, &ctx.accounts.org) {
+        (Some(seat), Some(org)) => {
+            if seat.org != org.key() {
+                return err!(ErrorCode::Unauthorized);
+            }
+            if let Some(tier) = org.active_tier(now) {
+                active.push((tier, org.expires_at));
+            }
+        }
+        (None, None) => {}
+        _ => return err!(ErrorCode::Unauthorized),
+    }
+    
+    match (&ctx.accounts.pass, &ctx.accounts.pass_token) {
+        (Some(pass), Some(pass_token)) => {
+            if pass_token.mint != pass.mint
+                || pass_token.owner != ctx.accounts.user.key()
+                || pass_token.amount != 1
+            {
+                return err!(ErrorCode::InvalidPass);
+            }
+            if let Some(tier) = pass.active_tier(now) {
+                active.push((tier, pass.expires_at));
+            }
+        }
+        (None, None) => {}
+        _ => return err!(ErrorCode::InvalidPass),
+    }
+    
+    if active.is_empty() {
+        return err!(ErrorCode::SubscriptionExpired);
+    }
+    let expires_at = active
+        .iter()
+        .filter(|(tier, _)| *tier == tier_config.tier_id)
+        .map(|(_, expires_at)| *expires_at)
+        .max()
+        .ok_or_else(|| error!(ErrorCode::InvalidTier))?;
+    
+    Ok(SubscriptionStatus {
+        tier: tier_config.tier_id,
+        expires_at,
+        features: tier_config.features,
+    })
+}
+
+/// Typed CPI helper for programs gating features on a subscription.
+/// `tier_config` is the config of the tier the user is expected to hold.
+/// Pass `None` for any account the caller does not have.
+#[cfg(feature = "cpi")]
+pub fn verify_subscription_cpi<'info>(
+    prediction_copilot_program: AccountInfo<'info>,
+    user: AccountInfo<'info>,
+    tier_config: AccountInfo<'info>,
+    subscription: Option<AccountInfo<'info>>,
+    seat: Option<AccountInfo<'info>>,
+    org: Option<AccountInfo<'info>>,
+    pass: Option<AccountInfo<'info>>,
+    pass_token: Option<AccountInfo<'info>>,
+) -> Result<SubscriptionStatus> {
+    let cpi_accounts = crate::cpi::accounts::VerifySubscription {
+        user,
+        tier_config,
+        subscription,
+        seat,
+        org,
+        pass,
+        pass_token,
+    };
+    let cpi_ctx = CpiContext::new(prediction_copilot_program, cpi_accounts);
+    Ok(crate::cpi::verify_subscription(cpi_ctx)?.get())
+}
@@ -0,0 +1,185 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::instructions::audit::log_admin_action;
+use crate::instructions::multisig::authorize_admin;
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// Approved multisig proposal, required when `admin` is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub new_admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.pending_admin == Some(new_admin.key()) @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, WhaleRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAdminTransfer<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// Approved multisig proposal, required when `admin` is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+}
+
+#[event]
+pub struct AdminTransferProposed {
+    pub current_admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminTransferAccepted {
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminTransferCancelled {
+    pub admin: Pubkey,
+    pub cancelled_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+    authorize_admin(
+        &ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &mut ctx.accounts.proposal,
+        AdminAction::ProposeAdmin { new_admin },
+    )?;
+    
+    let config = &mut ctx.accounts.config;
+    config.pending_admin = Some(new_admin);
+    
+    emit!(AdminTransferProposed {
+        current_admin: config.admin,
+        pending_admin: new_admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::ProposeAdmin,
+        ctx.accounts.admin.key(),
+        new_admin,
+    )?;
+    
+    Ok(())
+}
+
+pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let registry = &mut ctx.accounts.registry;
+    let new_admin = ctx.accounts.new_admin.key();
+    let old_admin = config.admin;
+    
+    // Config and registry always rotate together
+    config.admin = new_admin;
+    config.pending_admin = None;
+    registry.admin = new_admin;
+    
+    emit!(AdminTransferAccepted {
+        old_admin,
+        new_admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::AcceptAdmin,
+        new_admin,
+        old_admin,
+    )?;
+    
+    Ok(())
+}
+
+pub fn cancel_admin_transfer(ctx: Context<CancelAdminTransfer>) -> Result<()> {
+    authorize_admin(
+        &ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &mut ctx.accounts.proposal,
+        AdminAction::CancelAdminTransfer,
+    )?;
+    
+    let config = &mut ctx.accounts.config;
+    
+    let cancelled_admin = match config.pending_admin {
+        Some(pending) => pending,
+        None => return err!(ErrorCode::NoPendingAdmin),
+    };
+    config.pending_admin = None;
+    
+    emit!(AdminTransferCancelled {
+        admin: config.admin,
+        cancelled_admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::CancelAdminTransfer,
+        ctx.accounts.admin.key(),
+        cancelled_admin,
+    )?;
+    
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::instructions::multisig::authorize_admin;
+
+#[derive(Accounts)]
+pub struct InitializeAdminLog<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// Approved multisig proposal, required when `admin` is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = AdminLog::LEN,
+        seeds = [b"admin_log"],
+        bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_admin_log(ctx: Context<InitializeAdminLog>) -> Result<()> {
+    authorize_admin(
+        &ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &mut ctx.accounts.proposal,
+        AdminAction::InitializeAdminLog,
+    )?;
+    
+    let mut admin_log = ctx.accounts.admin_log.load_init()?;
+    admin_log.total_entries = 0;
+    admin_log.bump = ctx.bumps.admin_log;
+    Ok(())
+}
+
+/// Appends an entry to the admin log ring buffer.
+pub fn log_admin_action(
+    admin_log: &AccountLoader<AdminLog>,
+    action: AdminLogAction,
+    actor: Pubkey,
+    target: Pubkey,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    admin_log.load_mut()?.append(AdminLogEntry {
+        actor,
+        target,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+        action: action as u8,
+        _padding: [0; 7],
+    });
+    Ok(())
+}
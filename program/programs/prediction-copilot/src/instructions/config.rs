@@ -1,5 +1,11 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use crate::program::PredictionCopilot;
 use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::instructions::audit::log_admin_action;
+use crate::instructions::multisig::authorize_admin;
+use crate::instructions::payment::reject_unsupported_mint;
 
 #[derive(Accounts)]
 pub struct InitializeConfig<'info> {
@@ -15,28 +21,479 @@ pub struct InitializeConfig<'info> {
     )]
     pub config: Account<'info, ProgramConfig>,
     
-    /// CHECK: Treasury address, validated by admin (can be any address for now)
-    pub treasury: UncheckedAccount<'info>,
+    #[account(token::mint = payment_mint)]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Initial accepted payment mint (USDC), SPL Token or Token-2022
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ ErrorCode::Unauthorized
+    )]
+    pub program: Program<'info, PredictionCopilot>,
+    
+    /// Only the upgrade authority can initialize, which rules out front-running a fresh deploy
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
     
     pub system_program: Program<'info, System>,
 }
 
-pub fn initialize_config(
-    ctx: Context<InitializeConfig>,
-    basic_price: u64,
-    pro_price: u64,
-    basic_duration: i64,
-    pro_duration: i64,
-) -> Result<()> {
+pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+    reject_unsupported_mint(&ctx.accounts.payment_mint.to_account_info())?;
+    
     let config = &mut ctx.accounts.config;
     config.admin = ctx.accounts.admin.key();
     config.treasury = ctx.accounts.treasury.key();
-    config.basic_price = basic_price;
-    config.pro_price = pro_price;
-    config.basic_duration = basic_duration;
-    config.pro_duration = pro_duration;
-    config.paused = false;
+    config.pause_flags = 0;
     config.bump = ctx.bumps.config;
+    config.pending_admin = None;
+    config.payment_mints = vec![PaymentMint {
+        mint: ctx.accounts.payment_mint.key(),
+        decimals: ctx.accounts.payment_mint.decimals,
+    }];
+    config.cancellation_fee_bps = 0;
+    config.refund_window = 0;
+    config.trials_enabled = false;
+    config.trial_tier = 0;
+    config.trial_duration = 0;
+    config.referral_commission_bps = 0;
+    config.price_lock_duration = 0;
+    config.renewal_grace_period = 0;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// New treasury, a token account for an accepted payment mint
+    #[account(
+        constraint = config.payment_mint(&treasury.mint).is_some() @ ErrorCode::UnsupportedPaymentMint
+    )]
+    pub treasury: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Approved multisig proposal, required when `admin` is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub admin: Pubkey,
+    pub old_treasury: Pubkey,
+    pub new_treasury: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn update_config(ctx: Context<UpdateConfig>) -> Result<()> {
+    let treasury = ctx.accounts.treasury.as_ref().map(|treasury| treasury.key());
+    authorize_admin(
+        &ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &mut ctx.accounts.proposal,
+        AdminAction::UpdateConfig { treasury },
+    )?;
+    
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::UpdateConfig,
+        ctx.accounts.admin.key(),
+        ctx.accounts.config.key(),
+    )?;
+    
+    apply_config_update(&mut ctx.accounts.config, ctx.accounts.admin.key(), treasury)
+}
+
+/// Writes the given fields, emitting `ConfigUpdated`.
+/// Shared by `update_config` and `execute_config_change`, which both validate
+/// the treasury as a token account for an accepted payment mint.
+pub fn apply_config_update(
+    config: &mut ProgramConfig,
+    admin: Pubkey,
+    treasury: Option<Pubkey>,
+) -> Result<()> {
+    // Snapshot current values for the event
+    let old_treasury = config.treasury;
+    
+    if let Some(treasury) = treasury {
+        config.treasury = treasury;
+    }
+    
+    emit!(ConfigUpdated {
+        admin,
+        old_treasury,
+        new_treasury: config.treasury,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPauseFlags<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// Approved multisig proposal, required when `admin` is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+}
+
+#[event]
+pub struct PauseFlagsUpdated {
+    pub admin: Pubkey,
+    pub old_flags: u8,
+    pub new_flags: u8,
+    pub timestamp: i64,
+}
+
+pub fn set_pause_flags(ctx: Context<SetPauseFlags>, pause_flags: u8) -> Result<()> {
+    if pause_flags & !ProgramConfig::PAUSE_ALL != 0 {
+        return err!(ErrorCode::InvalidPauseFlags);
+    }
+    
+    authorize_admin(
+        &ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &mut ctx.accounts.proposal,
+        AdminAction::SetPauseFlags { pause_flags },
+    )?;
+    
+    let config = &mut ctx.accounts.config;
+    let old_flags = config.pause_flags;
+    config.pause_flags = pause_flags;
+    
+    emit!(PauseFlagsUpdated {
+        admin: ctx.accounts.admin.key(),
+        old_flags,
+        new_flags: pause_flags,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::SetPauseFlags,
+        ctx.accounts.admin.key(),
+        ctx.accounts.config.key(),
+    )?;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetRefundPolicy<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// Approved multisig proposal, required when `admin` is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+}
+
+#[event]
+pub struct RefundPolicyUpdated {
+    pub admin: Pubkey,
+    pub cancellation_fee_bps: u16,
+    pub refund_window: i64,
+    pub timestamp: i64,
+}
+
+pub fn set_refund_policy(
+    ctx: Context<SetRefundPolicy>,
+    cancellation_fee_bps: u16,
+    refund_window: i64,
+) -> Result<()> {
+    if cancellation_fee_bps > ProgramConfig::MAX_CANCELLATION_FEE_BPS {
+        return err!(ErrorCode::InvalidRefundPolicy);
+    }
+    if refund_window < 0 {
+        return err!(ErrorCode::InvalidRefundPolicy);
+    }
+    
+    authorize_admin(
+        &ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &mut ctx.accounts.proposal,
+        AdminAction::SetRefundPolicy { cancellation_fee_bps, refund_window },
+    )?;
+    
+    let config = &mut ctx.accounts.config;
+    config.cancellation_fee_bps = cancellation_fee_bps;
+    config.refund_window = refund_window;
+    
+    emit!(RefundPolicyUpdated {
+        admin: ctx.accounts.admin.key(),
+        cancellation_fee_bps,
+        refund_window,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::SetRefundPolicy,
+        ctx.accounts.admin.key(),
+        ctx.accounts.config.key(),
+    )?;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetTrialConfig<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// Approved multisig proposal, required when `admin` is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+}
+
+#[event]
+pub struct TrialConfigUpdated {
+    pub admin: Pubkey,
+    pub trials_enabled: bool,
+    pub trial_tier: u8,
+    pub trial_duration: i64,
+    pub timestamp: i64,
+}
+
+pub fn set_trial_config(
+    ctx: Context<SetTrialConfig>,
+    trials_enabled: bool,
+    trial_tier: u8,
+    trial_duration: i64,
+) -> Result<()> {
+    if trials_enabled {
+        // The tier itself is checked against its TierConfig in start_trial
+        if trial_tier == 0 || trial_tier > TierConfig::MAX_TIER_ID {
+            return err!(ErrorCode::InvalidTier);
+        }
+        if trial_duration <= 0 {
+            return err!(ErrorCode::InvalidDuration);
+        }
+    }
+    
+    authorize_admin(
+        &ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &mut ctx.accounts.proposal,
+        AdminAction::SetTrialConfig { trials_enabled, trial_tier, trial_duration },
+    )?;
+    
+    let config = &mut ctx.accounts.config;
+    config.trials_enabled = trials_enabled;
+    config.trial_tier = trial_tier;
+    config.trial_duration = trial_duration;
+    
+    emit!(TrialConfigUpdated {
+        admin: ctx.accounts.admin.key(),
+        trials_enabled,
+        trial_tier,
+        trial_duration,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::SetTrialConfig,
+        ctx.accounts.admin.key(),
+        ctx.accounts.config.key(),
+    )?;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetReferralCommission<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// Approved multisig proposal, required when `admin` is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+}
+
+#[event]
+pub struct ReferralCommissionUpdated {
+    pub admin: Pubkey,
+    pub old_bps: u16,
+    pub new_bps: u16,
+    pub timestamp: i64,
+}
+
+pub fn set_referral_commission(
+    ctx: Context<SetReferralCommission>,
+    referral_commission_bps: u16,
+) -> Result<()> {
+    if referral_commission_bps > ProgramConfig::MAX_REFERRAL_COMMISSION_BPS {
+        return err!(ErrorCode::InvalidReferralCommission);
+    }
+    
+    authorize_admin(
+        &ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &mut ctx.accounts.proposal,
+        AdminAction::SetReferralCommission { referral_commission_bps },
+    )?;
+    
+    let config = &mut ctx.accounts.config;
+    let old_bps = config.referral_commission_bps;
+    config.referral_commission_bps = referral_commission_bps;
+    
+    emit!(ReferralCommissionUpdated {
+        admin: ctx.accounts.admin.key(),
+        old_bps,
+        new_bps: referral_commission_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::SetReferralCommission,
+        ctx.accounts.admin.key(),
+        ctx.accounts.config.key(),
+    )?;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPriceLockPolicy<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// Approved multisig proposal, required when `admin` is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+}
+
+#[event]
+pub struct PriceLockPolicyUpdated {
+    pub admin: Pubkey,
+    pub price_lock_duration: i64,
+    pub renewal_grace_period: i64,
+    pub timestamp: i64,
+}
+
+/// Applies to locks started after the change, existing locks keep their expiry.
+pub fn set_price_lock_policy(
+    ctx: Context<SetPriceLockPolicy>,
+    price_lock_duration: i64,
+    renewal_grace_period: i64,
+) -> Result<()> {
+    if !(0..=ProgramConfig::MAX_PRICE_LOCK_DURATION).contains(&price_lock_duration)
+        || !(0..=ProgramConfig::MAX_RENEWAL_GRACE_PERIOD).contains(&renewal_grace_period)
+    {
+        return err!(ErrorCode::InvalidPriceLockPolicy);
+    }
+    
+    authorize_admin(
+        &ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &mut ctx.accounts.proposal,
+        AdminAction::SetPriceLockPolicy { price_lock_duration, renewal_grace_period },
+    )?;
+    
+    let config = &mut ctx.accounts.config;
+    config.price_lock_duration = price_lock_duration;
+    config.renewal_grace_period = renewal_grace_period;
+    
+    emit!(PriceLockPolicyUpdated {
+        admin: ctx.accounts.admin.key(),
+        price_lock_duration,
+        renewal_grace_period,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::SetPriceLockPolicy,
+        ctx.accounts.admin.key(),
+        ctx.accounts.config.key(),
+    )?;
     
     Ok(())
 }
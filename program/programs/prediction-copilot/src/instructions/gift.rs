@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::instructions::subscription::{credit_subscription, locked_tier_terms, start_price_lock};
+
+#[derive(Accounts)]
+#[instruction(tier: u8, recipient: Pubkey, gift_id: u64)]
+pub struct GiftSubscription<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = Subscription::LEN,
+        seeds = [b"subscription", recipient.as_ref()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = GiftReceipt::LEN,
+        seeds = [b"gift", payer.key().as_ref(), recipient.as_ref(), &gift_id.to_le_bytes()],
+        bump
+    )]
+    pub gift_receipt: Account<'info, GiftReceipt>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_SUBSCRIPTIONS) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        seeds = [b"tier", &[tier]],
+        bump = tier_config.bump,
+        constraint = tier_config.active @ ErrorCode::TierInactive
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    #[account(
+        mut,
+        constraint = payer_usdc.owner == payer.key() @ ErrorCode::Unauthorized,
+        constraint = payer_usdc.mint == usdc_mint.key() @ ErrorCode::InsufficientBalance
+    )]
+    pub payer_usdc: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", usdc_mint.key().as_ref()],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = config,
+        token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = config.payment_mint(&usdc_mint.key()).is_some() @ ErrorCode::UnsupportedPaymentMint
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    /// SPL Token or Token-2022, whichever owns `usdc_mint`
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct SubscriptionGifted {
+    pub payer: Pubkey,
+    pub recipient: Pubkey,
+    pub receipt: Pubkey,
+    pub tier: u8,
+    pub amount: u64,
+    pub mint: Pubkey,
+    pub memo_hash: Option<[u8; 32]>,
+    pub expires_at: i64,
+}
+
+pub fn gift_subscription(
+    ctx: Context<GiftSubscription>,
+    tier: u8,
+    recipient: Pubkey,
+    _gift_id: u64,
+    memo_hash: Option<[u8; 32]>,
+) -> Result<()> {
+    // Paying for one's own subscription goes through subscribe, which pays
+    // the bound referrer
+    if recipient == ctx.accounts.payer.key() {
+        return err!(ErrorCode::GiftToSelf);
+    }
+    
+    let config = &ctx.accounts.config;
+    let mint = ctx.accounts.usdc_mint.key();
+    let now = Clock::get()?.unix_timestamp;
+    let (price, duration) =
+        locked_tier_terms(config, &ctx.accounts.subscription, &ctx.accounts.tier_config, &mint, now)?;
+    
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.payer_usdc.to_account_info(),
+        mint: ctx.accounts.usdc_mint.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.payer.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    token_interface::transfer_checked(cpi_ctx, price, ctx.accounts.usdc_mint.decimals)?;
+    
+    start_price_lock(config, &mut ctx.accounts.subscription, &ctx.accounts.tier_config, &mint, now)?;
+    // The payer funded this period, so the recipient cannot cash it out
+    let expires_at = credit_subscription(
+        &mut ctx.accounts.subscription,
+        recipient,
+        tier,
+        0,
+        duration,
+        mint,
+        ctx.bumps.subscription,
+        now,
+    )?;
+    
+    let receipt = &mut ctx.accounts.gift_receipt;
+    receipt.payer = ctx.accounts.payer.key();
+    receipt.recipient = recipient;
+    receipt.tier = tier;
+    receipt.amount = price;
+    receipt.mint = mint;
+    receipt.memo_hash = memo_hash;
+    receipt.expires_at = expires_at;
+    receipt.created_at = now;
+    receipt.bump = ctx.bumps.gift_receipt;
+    
+    emit!(SubscriptionGifted {
+        payer: receipt.payer,
+        recipient,
+        receipt: receipt.key(),
+        tier,
+        amount: price,
+        mint,
+        memo_hash,
+        expires_at,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,266 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_lang::Discriminator;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use crate::program::PredictionCopilot;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::instructions::payment::reject_unsupported_mint;
+
+#[derive(Accounts)]
+pub struct MigrateSubscription<'info> {
+    /// Pays the extra rent, anyone can migrate any subscription
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: Wallet the subscription belongs to, only used for the seeds
+    pub user: UncheckedAccount<'info>,
+    
+    /// CHECK: Subscription in an earlier, shorter layout that `Account` cannot
+    /// deserialize yet. The discriminator is checked in the handler.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"subscription", user.key().as_ref()],
+        bump
+    )]
+    pub subscription: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct SubscriptionMigrated {
+    pub user: Pubkey,
+    pub old_len: u32,
+    pub new_len: u32,
+}
+
+/// Grows a subscription created under an earlier layout to `Subscription::LEN`.
+/// Fields are only ever added after the original ones, so the zero-filled
+/// tail reads as no refundable periods, no referrer and no price lock.
+/// Already migrated subscriptions are left alone.
+pub fn migrate_subscription(ctx: Context<MigrateSubscription>) -> Result<()> {
+    let subscription = ctx.accounts.subscription.to_account_info();
+    let old_len = subscription.data_len();
+    
+    {
+        let data = subscription.try_borrow_data()?;
+        if old_len < 8 || data[..8] != Subscription::DISCRIMINATOR {
+            return err!(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch);
+        }
+    }
+    if old_len >= Subscription::LEN {
+        return Ok(());
+    }
+    
+    let rent = Rent::get()?.minimum_balance(Subscription::LEN);
+    let top_up = rent.saturating_sub(subscription.lamports());
+    if top_up > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.payer.to_account_info(),
+            to: subscription.clone(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, top_up)?;
+    }
+    subscription.realloc(Subscription::LEN, true)?;
+    
+    // Fail here rather than on the subscriber's next payment
+    Subscription::try_deserialize(&mut &subscription.try_borrow_data()?[..])?;
+    
+    emit!(SubscriptionMigrated {
+        user: ctx.accounts.user.key(),
+        old_len: old_len as u32,
+        new_len: Subscription::LEN as u32,
+    });
+    
+    Ok(())
+}
+
+/// `ProgramConfig` as first deployed, with fixed Basic (1) and Pro (2) terms.
+#[derive(AnchorDeserialize)]
+struct LegacyProgramConfig {
+    admin: Pubkey,
+    _treasury: Pubkey,
+    basic_price: u64,
+    pro_price: u64,
+    basic_duration: i64,
+    pro_duration: i64,
+    paused: bool,
+    _bump: u8,
+}
+
+impl LegacyProgramConfig {
+    const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 1; // 106
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    /// CHECK: Config in the legacy layout, parsed and rewritten in the handler
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = TierConfig::LEN,
+        seeds = [b"tier", 1u8.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub basic_tier: Account<'info, TierConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = TierConfig::LEN,
+        seeds = [b"tier", 2u8.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pro_tier: Account<'info, TierConfig>,
+    
+    /// The legacy treasury was never checked to be a token account, so it is set again
+    #[account(token::mint = payment_mint)]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Mint the legacy prices were charged in (USDC)
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ ErrorCode::Unauthorized
+    )]
+    pub program: Program<'info, PredictionCopilot>,
+    
+    /// Only the upgrade authority can migrate, same as initialize_config
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct ConfigMigrated {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub payment_mint: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Rewrites a config deployed before tiers and payment mints into the
+/// current layout and seeds TierConfig 1 and 2 with the legacy Basic and Pro
+/// terms, so existing tier-1/2 subscribers keep renewing. The legacy pause
+/// switch becomes `PAUSE_ALL`; every newer setting starts off.
+/// Fresh deploys skip this and create their tiers with `create_tier`.
+pub fn migrate_config(
+    ctx: Context<MigrateConfig>,
+    basic_features: u32,
+    pro_features: u32,
+) -> Result<()> {
+    reject_unsupported_mint(&ctx.accounts.payment_mint.to_account_info())?;
+    
+    let config_info = ctx.accounts.config.to_account_info();
+    let legacy = {
+        let data = config_info.try_borrow_data()?;
+        if data.len() != LegacyProgramConfig::LEN || data[..8] != ProgramConfig::DISCRIMINATOR {
+            return err!(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch);
+        }
+        LegacyProgramConfig::deserialize(&mut &data[8..])?
+    };
+    
+    let config = ProgramConfig {
+        admin: legacy.admin,
+        treasury: ctx.accounts.treasury.key(),
+        pause_flags: if legacy.paused { ProgramConfig::PAUSE_ALL } else { 0 },
+        bump: ctx.bumps.config,
+        pending_admin: None,
+        payment_mints: vec![PaymentMint {
+            mint: ctx.accounts.payment_mint.key(),
+            decimals: ctx.accounts.payment_mint.decimals,
+        }],
+        cancellation_fee_bps: 0,
+        refund_window: 0,
+        trials_enabled: false,
+        trial_tier: 0,
+        trial_duration: 0,
+        referral_commission_bps: 0,
+        price_lock_duration: 0,
+        renewal_grace_period: 0,
+    };
+    
+    let rent = Rent::get()?.minimum_balance(ProgramConfig::LEN);
+    let top_up = rent.saturating_sub(config_info.lamports());
+    if top_up > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.admin.to_account_info(),
+            to: config_info.clone(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, top_up)?;
+    }
+    config_info.realloc(ProgramConfig::LEN, true)?;
+    config.try_serialize(&mut &mut config_info.try_borrow_mut_data()?[..])?;
+    
+    // Legacy prices were USDC amounts (6 decimals), which is what usd_price holds
+    seed_legacy_tier(
+        &mut ctx.accounts.basic_tier,
+        1,
+        b"Basic",
+        legacy.basic_price,
+        legacy.basic_duration,
+        basic_features,
+        ctx.bumps.basic_tier,
+    )?;
+    seed_legacy_tier(
+        &mut ctx.accounts.pro_tier,
+        2,
+        b"Pro",
+        legacy.pro_price,
+        legacy.pro_duration,
+        pro_features,
+        ctx.bumps.pro_tier,
+    )?;
+    
+    emit!(ConfigMigrated {
+        admin: legacy.admin,
+        treasury: ctx.accounts.treasury.key(),
+        payment_mint: ctx.accounts.payment_mint.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+fn seed_legacy_tier(
+    tier_config: &mut TierConfig,
+    tier_id: u8,
+    label: &[u8],
+    usd_price: u64,
+    duration: i64,
+    features: u32,
+    bump: u8,
+) -> Result<()> {
+    require!(usd_price > 0, ErrorCode::InvalidPrice);
+    require!(duration > 0, ErrorCode::InvalidDuration);
+    
+    let mut name = [0u8; 32];
+    name[..label.len()].copy_from_slice(label);
+    tier_config.tier_id = tier_id;
+    tier_config.name = name;
+    tier_config.usd_price = usd_price;
+    tier_config.duration = duration;
+    tier_config.features = features;
+    tier_config.active = true;
+    tier_config.bump = bump;
+    
+    Ok(())
+}
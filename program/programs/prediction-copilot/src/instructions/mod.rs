@@ -1,11 +1,47 @@
 pub mod config;
+pub mod admin;
+pub mod roles;
+pub mod multisig;
+pub mod timelock;
+pub mod payment;
+pub mod treasury;
+pub mod audit;
 pub mod subscription;
+pub mod gift;
+pub mod renewal;
+pub mod trial;
+pub mod promo;
+pub mod referral;
+pub mod org;
+pub mod access;
+pub mod sol_payment;
+pub mod pass;
+pub mod tier;
+pub mod migrate;
 pub mod registry;
 pub mod signal;
 pub mod movement; // [NEW]
 
 pub use config::*;
+pub use admin::*;
+pub use roles::*;
+pub use multisig::*;
+pub use timelock::*;
+pub use payment::*;
+pub use treasury::*;
+pub use audit::*;
 pub use subscription::*;
+pub use gift::*;
+pub use renewal::*;
+pub use trial::*;
+pub use promo::*;
+pub use referral::*;
+pub use org::*;
+pub use access::*;
+pub use sol_payment::*;
+pub use pass::*;
+pub use tier::*;
+pub use migrate::*;
 pub use registry::*;
 pub use signal::*;
 pub use movement::*; // [NEW]
@@ -6,14 +6,20 @@ use crate::errors::ErrorCode;
 #[instruction(polygon_address: [u8; 20])]
 pub struct RecordWhaleMovement<'info> {
     #[account(mut)]
-    pub admin: Signer<'info>, // Only Admin (Keeper) can record movements for now to prevent spam
+    pub admin: Signer<'info>, // Only Keeper role holders can record movements to prevent spam
 
     #[account(
         seeds = [b"config"],
         bump = config.bump,
-        constraint = config.admin == admin.key() @ ErrorCode::Unauthorized
+        constraint = !config.is_paused(ProgramConfig::PAUSE_MOVEMENTS) @ ErrorCode::ProgramPaused
     )]
     pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        seeds = [b"role", &[Role::Keeper as u8], admin.key().as_ref()],
+        bump = role_grant.bump
+    )]
+    pub role_grant: Account<'info, RoleGrant>,
 
     #[account(
         seeds = [b"trader", polygon_address.as_ref()],
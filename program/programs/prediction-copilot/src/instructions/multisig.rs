@@ -0,0 +1,272 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::instructions::audit::log_admin_action;
+
+#[derive(Accounts)]
+pub struct CreateMultisig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// Approved multisig proposal, required when `admin` is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = Multisig::LEN,
+        seeds = [b"multisig"],
+        bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"multisig"],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = MultisigProposal::LEN,
+        seeds = [b"proposal", multisig.key().as_ref(), &multisig.proposal_count.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, MultisigProposal>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    pub signer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"multisig"],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+    
+    #[account(
+        mut,
+        seeds = [b"proposal", multisig.key().as_ref(), &proposal.index.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = proposal.multisig == multisig.key() @ ErrorCode::Unauthorized
+    )]
+    pub proposal: Account<'info, MultisigProposal>,
+}
+
+#[event]
+pub struct MultisigCreated {
+    pub multisig: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalApproved {
+    pub proposal: Pubkey,
+    pub signer: Pubkey,
+    pub approval_count: u8,
+    pub approved: bool,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub proposal: Pubkey,
+    pub index: u64,
+    pub executor: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn create_multisig(
+    ctx: Context<CreateMultisig>,
+    signers: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    if signers.is_empty() || signers.len() > Multisig::MAX_SIGNERS {
+        return err!(ErrorCode::InvalidMultisigSigners);
+    }
+    for (i, signer) in signers.iter().enumerate() {
+        if signers[..i].contains(signer) {
+            return err!(ErrorCode::InvalidMultisigSigners);
+        }
+    }
+    if threshold == 0 || threshold as usize > signers.len() {
+        return err!(ErrorCode::InvalidThreshold);
+    }
+    
+    authorize_admin(
+        &ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &mut ctx.accounts.proposal,
+        AdminAction::CreateMultisig { signers: signers.clone(), threshold },
+    )?;
+    
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.signers = signers;
+    multisig.threshold = threshold;
+    multisig.proposal_count = 0;
+    multisig.bump = ctx.bumps.multisig;
+    
+    emit!(MultisigCreated {
+        multisig: multisig.key(),
+        signers: multisig.signers.clone(),
+        threshold,
+    });
+    
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::CreateMultisig,
+        ctx.accounts.admin.key(),
+        ctx.accounts.multisig.key(),
+    )?;
+    
+    Ok(())
+}
+
+pub fn create_proposal(ctx: Context<CreateProposal>, action: AdminAction) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+    let proposer = ctx.accounts.proposer.key();
+    let signer_index = signer_index(multisig, &proposer)?;
+    
+    let proposal = &mut ctx.accounts.proposal;
+    let now = Clock::get()?.unix_timestamp;
+    
+    // Proposer's approval is implied
+    proposal.multisig = multisig.key();
+    proposal.index = multisig.proposal_count;
+    proposal.proposer = proposer;
+    proposal.action = action.clone();
+    proposal.approvals = 1 << signer_index;
+    proposal.approved = multisig.threshold <= 1;
+    proposal.executed = false;
+    proposal.created_at = now;
+    proposal.bump = ctx.bumps.proposal;
+    
+    multisig.proposal_count += 1;
+    
+    emit!(ProposalCreated {
+        proposal: proposal.key(),
+        index: proposal.index,
+        proposer,
+        action,
+        timestamp: now,
+    });
+    
+    Ok(())
+}
+
+pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let proposal = &mut ctx.accounts.proposal;
+    let signer = ctx.accounts.signer.key();
+    let signer_index = signer_index(multisig, &signer)?;
+    
+    if proposal.executed {
+        return err!(ErrorCode::ProposalAlreadyExecuted);
+    }
+    
+    proposal.approvals |= 1 << signer_index;
+    let approval_count = proposal.approvals.count_ones() as u8;
+    if approval_count >= multisig.threshold {
+        proposal.approved = true;
+    }
+    
+    emit!(ProposalApproved {
+        proposal: proposal.key(),
+        signer,
+        approval_count,
+        approved: proposal.approved,
+    });
+    
+    Ok(())
+}
+
+/// Passes if `signer` is the config admin. Otherwise `proposal` must be an
+/// approved, unexecuted multisig proposal for exactly `action`, which is
+/// then marked executed so it cannot be replayed.
+pub fn authorize_admin(
+    config: &ProgramConfig,
+    signer: Pubkey,
+    proposal: &mut Option<Account<MultisigProposal>>,
+    action: AdminAction,
+) -> Result<()> {
+    if signer == config.admin {
+        return Ok(());
+    }
+    execute_proposal(signer, proposal, action)
+}
+
+/// Consumes an approved multisig proposal matching `action`.
+pub fn execute_proposal(
+    executor: Pubkey,
+    proposal: &mut Option<Account<MultisigProposal>>,
+    action: AdminAction,
+) -> Result<()> {
+    let proposal = match proposal.as_mut() {
+        Some(proposal) => proposal,
+        None => return err!(ErrorCode::Unauthorized),
+    };
+    
+    if !proposal.approved {
+        return err!(ErrorCode::ProposalNotApproved);
+    }
+    if proposal.executed {
+        return err!(ErrorCode::ProposalAlreadyExecuted);
+    }
+    if proposal.action != action {
+        return err!(ErrorCode::ProposalActionMismatch);
+    }
+    
+    proposal.executed = true;
+    
+    emit!(ProposalExecuted {
+        proposal: proposal.key(),
+        index: proposal.index,
+        executor,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+fn signer_index(multisig: &Multisig, signer: &Pubkey) -> Result<usize> {
+    match multisig.signers.iter().position(|key| key == signer) {
+        Some(index) => Ok(index),
+        None => err!(ErrorCode::NotMultisigSigner),
+    }
+}
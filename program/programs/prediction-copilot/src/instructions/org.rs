@@ -0,0 +1,376 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::instructions::subscription::{discounted_tier_terms, tier_terms};
+
+#[derive(Accounts)]
+#[instruction(tier: u8)]
+pub struct PurchaseOrgSubscription<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = OrgSubscription::LEN,
+        seeds = [b"org", owner.key().as_ref()],
+        bump
+    )]
+    pub org: Account<'info, OrgSubscription>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_SUBSCRIPTIONS) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        seeds = [b"tier", &[tier]],
+        bump = tier_config.bump,
+        constraint = tier_config.active @ ErrorCode::TierInactive
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    #[account(
+        mut,
+        constraint = owner_usdc.owner == owner.key() @ ErrorCode::Unauthorized,
+        constraint = owner_usdc.mint == usdc_mint.key() @ ErrorCode::InsufficientBalance
+    )]
+    pub owner_usdc: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", usdc_mint.key().as_ref()],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = config,
+        token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = config.payment_mint(&usdc_mint.key()).is_some() @ ErrorCode::UnsupportedPaymentMint
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    /// SPL Token or Token-2022, whichever owns `usdc_mint`
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct OrgSubscriptionPurchased {
+    pub org: Pubkey,
+    pub owner: Pubkey,
+    pub tier: u8,
+    pub seats: u16,
+    pub amount: u64,
+    pub mint: Pubkey,
+    pub expires_at: i64,
+}
+
+pub fn purchase_org_subscription(
+    ctx: Context<PurchaseOrgSubscription>,
+    tier: u8,
+    seats: u16,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let org = &mut ctx.accounts.org;
+    let mint = ctx.accounts.usdc_mint.key();
+    let now = Clock::get()?.unix_timestamp;
+    
+    if seats == 0 || seats > OrgSubscription::MAX_SEATS {
+        return err!(ErrorCode::InvalidSeatCount);
+    }
+    let tier_config = &ctx.accounts.tier_config;
+    let (price, duration) = org_tier_terms(config, org, tier_config, &mint, now)?;
+    let lock_holds = org.locked_price(tier, now, config.renewal_grace_period).is_some();
+    
+    let is_extension = org.expires_at > now;
+    if is_extension {
+        // Renewing an active org keeps its terms, seats grow via add_org_seats
+        if org.tier != tier {
+            return err!(ErrorCode::InvalidTier);
+        }
+        if org.seats != seats {
+            return err!(ErrorCode::InvalidSeatCount);
+        }
+        if org.mint != mint {
+            return err!(ErrorCode::PaymentMintMismatch);
+        }
+    } else if seats < org.seats_used {
+        // Members keep their seat PDAs across a lapse
+        return err!(ErrorCode::InvalidSeatCount);
+    }
+    
+    let amount = price.checked_mul(seats as u64).ok_or(ErrorCode::MathOverflow)?;
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.owner_usdc.to_account_info(),
+        mint: ctx.accounts.usdc_mint.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.usdc_mint.decimals)?;
+    
+    if is_extension {
+        org.expires_at += duration;
+    } else {
+        org.owner = ctx.accounts.owner.key();
+        org.tier = tier;
+        org.seats = seats;
+        org.started_at = now;
+        org.expires_at = now + duration;
+        org.bump = ctx.bumps.org;
+        org.mint = mint;
+        org.total_paid = 0;
+        if !lock_holds {
+            org.locked_usd_price = tier_config.usd_price;
+            org.locked_mint = mint;
+            org.locked_mint_price = tier_terms(config, tier_config, &mint)?.0;
+            org.price_lock_expires_at = if config.price_lock_duration > 0 {
+                now.saturating_add(config.price_lock_duration)
+            } else {
+                0
+            };
+        }
+    }
+    org.total_paid = org.total_paid.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+    
+    emit!(OrgSubscriptionPurchased {
+        org: org.key(),
+        owner: org.owner,
+        tier,
+        seats,
+        amount,
+        mint,
+        expires_at: org.expires_at,
+    });
+    
+    Ok(())
+}
+
+/// Per-seat price and duration of `tier_config` for `org`, at its
+/// grandfathered price while the org's lock holds.
+fn org_tier_terms(
+    config: &ProgramConfig,
+    org: &OrgSubscription,
+    tier_config: &TierConfig,
+    mint: &Pubkey,
+    now: i64,
+) -> Result<(u64, i64)> {
+    let usd_price = match org.locked_price(tier_config.tier_id, now, config.renewal_grace_period) {
+        Some(locked) => locked.min(tier_config.usd_price),
+        None => tier_config.usd_price,
+    };
+    let locked_mint_price = org.locked_mint_price(tier_config.tier_id, mint, now, config.renewal_grace_period);
+    discounted_tier_terms(config, tier_config, mint, usd_price, locked_mint_price)
+}
+
+#[derive(Accounts)]
+pub struct AddOrgSeats<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"org", owner.key().as_ref()],
+        bump = org.bump,
+        has_one = owner @ ErrorCode::Unauthorized
+    )]
+    pub org: Account<'info, OrgSubscription>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_SUBSCRIPTIONS) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        seeds = [b"tier", &[org.tier]],
+        bump = tier_config.bump,
+        constraint = tier_config.active @ ErrorCode::TierInactive
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    #[account(
+        mut,
+        constraint = owner_usdc.owner == owner.key() @ ErrorCode::Unauthorized,
+        constraint = owner_usdc.mint == usdc_mint.key() @ ErrorCode::InsufficientBalance
+    )]
+    pub owner_usdc: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", usdc_mint.key().as_ref()],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = config,
+        token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = config.payment_mint(&usdc_mint.key()).is_some() @ ErrorCode::UnsupportedPaymentMint
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    /// SPL Token or Token-2022, whichever owns `usdc_mint`
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event]
+pub struct OrgSeatsAdded {
+    pub org: Pubkey,
+    pub added: u16,
+    pub seats: u16,
+    pub amount: u64,
+    pub mint: Pubkey,
+}
+
+pub fn add_org_seats(ctx: Context<AddOrgSeats>, additional: u16) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let org = &mut ctx.accounts.org;
+    let mint = ctx.accounts.usdc_mint.key();
+    let now = Clock::get()?.unix_timestamp;
+    
+    if org.expires_at <= now {
+        return err!(ErrorCode::SubscriptionExpired);
+    }
+    if org.mint != mint {
+        return err!(ErrorCode::PaymentMintMismatch);
+    }
+    let seats = org.seats.saturating_add(additional);
+    if additional == 0 || seats > OrgSubscription::MAX_SEATS {
+        return err!(ErrorCode::InvalidSeatCount);
+    }
+    
+    // New seats only pay for the time left on the current period
+    let (price, duration) = org_tier_terms(config, org, &ctx.accounts.tier_config, &mint, now)?;
+    let remaining = (org.expires_at - now) as u128;
+    let amount = u64::try_from(price as u128 * additional as u128 * remaining / duration as u128)
+        .map_err(|_| ErrorCode::MathOverflow)?;
+    
+    if amount > 0 {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.owner_usdc.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.usdc_mint.decimals)?;
+    }
+    
+    org.seats = seats;
+    org.total_paid = org.total_paid.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+    
+    emit!(OrgSeatsAdded {
+        org: org.key(),
+        added: additional,
+        seats,
+        amount,
+        mint,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(member: Pubkey)]
+pub struct AddOrgMember<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"org", owner.key().as_ref()],
+        bump = org.bump,
+        has_one = owner @ ErrorCode::Unauthorized
+    )]
+    pub org: Account<'info, OrgSubscription>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = OrgSeat::LEN,
+        seeds = [b"seat", org.key().as_ref(), member.as_ref()],
+        bump
+    )]
+    pub seat: Account<'info, OrgSeat>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct OrgMemberAdded {
+    pub org: Pubkey,
+    pub member: Pubkey,
+    pub seats_used: u16,
+}
+
+pub fn add_org_member(ctx: Context<AddOrgMember>, member: Pubkey) -> Result<()> {
+    let org = &mut ctx.accounts.org;
+    if org.seats_used >= org.seats {
+        return err!(ErrorCode::NoSeatsAvailable);
+    }
+    org.seats_used += 1;
+    
+    let seat = &mut ctx.accounts.seat;
+    seat.org = org.key();
+    seat.member = member;
+    seat.added_at = Clock::get()?.unix_timestamp;
+    seat.bump = ctx.bumps.seat;
+    
+    emit!(OrgMemberAdded {
+        org: seat.org,
+        member,
+        seats_used: org.seats_used,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RemoveOrgMember<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"org", owner.key().as_ref()],
+        bump = org.bump,
+        has_one = owner @ ErrorCode::Unauthorized
+    )]
+    pub org: Account<'info, OrgSubscription>,
+    
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"seat", org.key().as_ref(), seat.member.as_ref()],
+        bump = seat.bump
+    )]
+    pub seat: Account<'info, OrgSeat>,
+}
+
+#[event]
+pub struct OrgMemberRemoved {
+    pub org: Pubkey,
+    pub member: Pubkey,
+    pub seats_used: u16,
+}
+
+pub fn remove_org_member(ctx: Context<RemoveOrgMember>) -> Result<()> {
+    let org = &mut ctx.accounts.org;
+    org.seats_used -= 1;
+    
+    emit!(OrgMemberRemoved {
+        org: org.key(),
+        member: ctx.accounts.seat.member,
+        seats_used: org.seats_used,
+    });
+    
+    Ok(())
+}
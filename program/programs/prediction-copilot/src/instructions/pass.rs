@@ -0,0 +1,403 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    self, Burn, CloseAccount, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked,
+};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::instructions::subscription::{
+    bound_referral_commission, credit_subscription, locked_tier_terms, tier_terms, ReferralCommissionPaid,
+};
+
+#[derive(Accounts)]
+#[instruction(tier: u8, pass_id: u64)]
+pub struct SubscribeAsPass<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = SubscriptionPass::LEN,
+        seeds = [b"pass", pass_mint.key().as_ref()],
+        bump
+    )]
+    pub pass: Account<'info, SubscriptionPass>,
+    
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"pass_mint", user.key().as_ref(), &pass_id.to_le_bytes()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = config,
+        mint::freeze_authority = config,
+        mint::token_program = token_program
+    )]
+    pub pass_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = user,
+        associated_token::mint = pass_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program
+    )]
+    pub user_pass_token: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_SUBSCRIPTIONS) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        seeds = [b"tier", &[tier]],
+        bump = tier_config.bump,
+        constraint = tier_config.active @ ErrorCode::TierInactive
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ ErrorCode::Unauthorized,
+        constraint = user_token_account.mint == payment_mint.key() @ ErrorCode::InsufficientBalance
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", payment_mint.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = config,
+        token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = config.payment_mint(&payment_mint.key()).is_some() @ ErrorCode::UnsupportedPaymentMint
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: The buyer's own subscription, if any. Its bound referrer earns
+    /// commission on the pass like on any other payment.
+    #[account(
+        seeds = [b"subscription", user.key().as_ref()],
+        bump
+    )]
+    pub user_subscription: UncheckedAccount<'info>,
+    
+    /// Referrer bound to `user_subscription`, passed together with `referrer_token_account`
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer_profile.wallet.as_ref()],
+        bump = referrer_profile.bump
+    )]
+    pub referrer_profile: Option<Account<'info, ReferrerProfile>>,
+    
+    /// Referrer's token account for the payment mint, receives the commission
+    #[account(mut)]
+    pub referrer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Owns both the payment mint and the new pass mint
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct PassMinted {
+    pub pass: Pubkey,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub tier: u8,
+    pub amount: u64,
+    pub payment_mint: Pubkey,
+    pub expires_at: i64,
+}
+
+pub fn subscribe_as_pass(ctx: Context<SubscribeAsPass>, tier: u8, _pass_id: u64) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let user = ctx.accounts.user.key();
+    let payment_mint = ctx.accounts.payment_mint.key();
+    let decimals = ctx.accounts.payment_mint.decimals;
+    let now = Clock::get()?.unix_timestamp;
+    
+    // 1. Priced like subscribe, so a buyer with a price lock on the tier
+    // pays the locked price
+    let user_subscription = ctx.accounts.user_subscription.to_account_info();
+    let user_subscription = if user_subscription.owner == &crate::ID {
+        Some(Subscription::try_deserialize(&mut &user_subscription.try_borrow_data()?[..])?)
+    } else {
+        None
+    };
+    let tier_config = &ctx.accounts.tier_config;
+    let (price, duration) = match &user_subscription {
+        Some(subscription) => locked_tier_terms(config, subscription, tier_config, &payment_mint, now)?,
+        None => tier_terms(config, tier_config, &payment_mint)?,
+    };
+    
+    // 2. A pass never binds a referrer, but the buyer's bound referrer is
+    // paid as if the buyer had subscribed. Otherwise minting a pass and
+    // redeeming it would skip the commission.
+    let (commission, _) = bound_referral_commission(
+        config,
+        user_subscription.and_then(|subscription| subscription.referrer),
+        false,
+        user,
+        &payment_mint,
+        price,
+        ctx.accounts.referrer_profile.as_mut(),
+        ctx.accounts.referrer_token_account.as_ref().map(|account| (account.owner, account.mint)),
+    )?;
+    
+    // 3. Pay into the vault, same as subscribe
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.user_token_account.to_account_info(),
+        mint: ctx.accounts.payment_mint.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    if price - commission > 0 {
+        token_interface::transfer_checked(cpi_ctx, price - commission, decimals)?;
+    }
+    
+    let referrer = (&ctx.accounts.referrer_profile, &ctx.accounts.referrer_token_account);
+    if let (Some(profile), Some(referrer_token_account)) = referrer {
+        if commission > 0 {
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                mint: ctx.accounts.payment_mint.to_account_info(),
+                to: referrer_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            token_interface::transfer_checked(cpi_ctx, commission, decimals)?;
+            
+            emit!(ReferralCommissionPaid {
+                referrer: profile.wallet,
+                user,
+                amount: commission,
+                mint: payment_mint,
+            });
+        }
+    }
+    
+    // 4. Mint the single pass token, the config PDA never mints again
+    let seeds = &[b"config".as_ref(), &[config.bump]];
+    let signer = &[&seeds[..]];
+    let cpi_accounts = MintTo {
+        mint: ctx.accounts.pass_mint.to_account_info(),
+        to: ctx.accounts.user_pass_token.to_account_info(),
+        authority: config.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    token_interface::mint_to(cpi_ctx, 1)?;
+    
+    // 5. Record the terms the pass carries
+    let pass = &mut ctx.accounts.pass;
+    pass.mint = ctx.accounts.pass_mint.key();
+    pass.tier = tier;
+    pass.expires_at = now + duration;
+    pass.created_at = now;
+    pass.original_owner = user;
+    pass.bump = ctx.bumps.pass;
+    
+    emit!(PassMinted {
+        pass: pass.key(),
+        mint: pass.mint,
+        owner: pass.original_owner,
+        tier,
+        amount: price,
+        payment_mint,
+        expires_at: pass.expires_at,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RedeemPass<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = holder,
+        seeds = [b"pass", pass_mint.key().as_ref()],
+        bump = pass.bump
+    )]
+    pub pass: Account<'info, SubscriptionPass>,
+    
+    #[account(mut, address = pass.mint @ ErrorCode::InvalidPass)]
+    pub pass_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = holder_pass_token.owner == holder.key() @ ErrorCode::InvalidPass,
+        constraint = holder_pass_token.mint == pass_mint.key() @ ErrorCode::InvalidPass,
+        constraint = holder_pass_token.amount == 1 @ ErrorCode::InvalidPass
+    )]
+    pub holder_pass_token: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = holder,
+        space = Subscription::LEN,
+        seeds = [b"subscription", holder.key().as_ref()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_SUBSCRIPTIONS) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct PassRedeemed {
+    pub pass: Pubkey,
+    pub mint: Pubkey,
+    pub holder: Pubkey,
+    pub tier: u8,
+    pub new_expires_at: i64,
+}
+
+/// Moves the remaining pass time onto the holder's subscription. The pass
+/// must be for the holder's current tier while that subscription is active;
+/// otherwise redeem it after the subscription lapses, or `change_tier` first.
+pub fn redeem_pass(ctx: Context<RedeemPass>) -> Result<()> {
+    let pass = &ctx.accounts.pass;
+    let now = Clock::get()?.unix_timestamp;
+    if pass.expires_at <= now {
+        return err!(ErrorCode::SubscriptionExpired);
+    }
+    if ctx.accounts.subscription.active_tier(now).is_some_and(|tier| tier != pass.tier) {
+        return err!(ErrorCode::PassTierMismatch);
+    }
+    
+    burn_and_close_pass_token(
+        &ctx.accounts.token_program,
+        &ctx.accounts.pass_mint,
+        &ctx.accounts.holder_pass_token,
+        &ctx.accounts.holder,
+    )?;
+    
+    // The remaining pass time moves onto the holder's wallet-bound subscription.
+    // It was paid for by the original owner, so it is credited as unpaid time
+    // the holder cannot cash out through cancel_subscription.
+    let holder = ctx.accounts.holder.key();
+    let subscription = &mut ctx.accounts.subscription;
+    let new_expires_at = credit_subscription(
+        subscription,
+        holder,
+        pass.tier,
+        0,
+        pass.expires_at - now,
+        pass.mint,
+        ctx.bumps.subscription,
+        now,
+    )?;
+    
+    emit!(PassRedeemed {
+        pass: pass.key(),
+        mint: pass.mint,
+        holder,
+        tier: pass.tier,
+        new_expires_at,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct BurnExpiredPass<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = holder,
+        seeds = [b"pass", pass_mint.key().as_ref()],
+        bump = pass.bump
+    )]
+    pub pass: Account<'info, SubscriptionPass>,
+    
+    #[account(mut, address = pass.mint @ ErrorCode::InvalidPass)]
+    pub pass_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = holder_pass_token.owner == holder.key() @ ErrorCode::InvalidPass,
+        constraint = holder_pass_token.mint == pass_mint.key() @ ErrorCode::InvalidPass,
+        constraint = holder_pass_token.amount == 1 @ ErrorCode::InvalidPass
+    )]
+    pub holder_pass_token: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event]
+pub struct PassBurned {
+    pub pass: Pubkey,
+    pub mint: Pubkey,
+    pub holder: Pubkey,
+}
+
+pub fn burn_expired_pass(ctx: Context<BurnExpiredPass>) -> Result<()> {
+    let pass = &ctx.accounts.pass;
+    if pass.expires_at > Clock::get()?.unix_timestamp {
+        return err!(ErrorCode::PassNotExpired);
+    }
+    
+    burn_and_close_pass_token(
+        &ctx.accounts.token_program,
+        &ctx.accounts.pass_mint,
+        &ctx.accounts.holder_pass_token,
+        &ctx.accounts.holder,
+    )?;
+    
+    emit!(PassBurned {
+        pass: pass.key(),
+        mint: pass.mint,
+        holder: ctx.accounts.holder.key(),
+    });
+    
+    Ok(())
+}
+
+/// Burns the holder's pass token and returns its rent to the holder.
+fn burn_and_close_pass_token<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    pass_mint: &InterfaceAccount<'info, Mint>,
+    holder_pass_token: &InterfaceAccount<'info, TokenAccount>,
+    holder: &Signer<'info>,
+) -> Result<()> {
+    let cpi_accounts = Burn {
+        mint: pass_mint.to_account_info(),
+        from: holder_pass_token.to_account_info(),
+        authority: holder.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+    token_interface::burn(cpi_ctx, 1)?;
+    
+    let cpi_accounts = CloseAccount {
+        account: holder_pass_token.to_account_info(),
+        destination: holder.to_account_info(),
+        authority: holder.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+    token_interface::close_account(cpi_ctx)
+}
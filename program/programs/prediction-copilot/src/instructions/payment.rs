@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{
+        permanent_delegate::PermanentDelegate, transfer_fee::TransferFeeConfig,
+        transfer_hook::TransferHook, BaseStateWithExtensions, StateWithExtensions,
+    },
+};
+use anchor_spl::token_interface::Mint;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::instructions::audit::log_admin_action;
+use crate::instructions::multisig::authorize_admin;
+
+#[derive(Accounts)]
+pub struct AddPaymentMint<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// SPL Token or Token-2022 mint without a transfer fee, hook or permanent delegate
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// Approved multisig proposal, required when `admin` is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+}
+
+#[derive(Accounts)]
+pub struct RemovePaymentMint<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// Approved multisig proposal, required when `admin` is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+}
+
+#[event]
+pub struct PaymentMintAdded {
+    pub mint: Pubkey,
+    pub decimals: u8,
+}
+
+#[event]
+pub struct PaymentMintRemoved {
+    pub mint: Pubkey,
+}
+
+pub fn add_payment_mint(ctx: Context<AddPaymentMint>) -> Result<()> {
+    let mint = &ctx.accounts.mint;
+    
+    authorize_admin(
+        &ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &mut ctx.accounts.proposal,
+        AdminAction::AddPaymentMint { mint: mint.key() },
+    )?;
+    
+    reject_unsupported_mint(&mint.to_account_info())?;
+    
+    let config = &mut ctx.accounts.config;
+    if config.payment_mint(&mint.key()).is_some() {
+        return err!(ErrorCode::PaymentMintAlreadyAccepted);
+    }
+    if config.payment_mints.len() >= ProgramConfig::MAX_PAYMENT_MINTS {
+        return err!(ErrorCode::TooManyPaymentMints);
+    }
+    
+    let entry = PaymentMint {
+        mint: mint.key(),
+        decimals: mint.decimals,
+    };
+    
+    emit!(PaymentMintAdded {
+        mint: entry.mint,
+        decimals: entry.decimals,
+    });
+    
+    config.payment_mints.push(entry);
+    
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::AddPaymentMint,
+        ctx.accounts.admin.key(),
+        ctx.accounts.mint.key(),
+    )?;
+    
+    Ok(())
+}
+
+pub fn remove_payment_mint(ctx: Context<RemovePaymentMint>, mint: Pubkey) -> Result<()> {
+    authorize_admin(
+        &ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &mut ctx.accounts.proposal,
+        AdminAction::RemovePaymentMint { mint },
+    )?;
+    
+    let config = &mut ctx.accounts.config;
+    let index = match config.payment_mints.iter().position(|entry| entry.mint == mint) {
+        Some(index) => index,
+        None => return err!(ErrorCode::UnsupportedPaymentMint),
+    };
+    
+    // Always keep at least one way to pay
+    if config.payment_mints.len() == 1 {
+        return err!(ErrorCode::CannotRemoveLastPaymentMint);
+    }
+    config.payment_mints.remove(index);
+    
+    emit!(PaymentMintRemoved { mint });
+    
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::RemovePaymentMint,
+        ctx.accounts.admin.key(),
+        mint,
+    )?;
+    
+    Ok(())
+}
+
+/// A transfer fee would leave the vault short of the credited price, and the
+/// fee authority can raise it at any time, so the extension is refused outright.
+/// A transfer hook can fail or reroute every vault transfer, and a permanent
+/// delegate can drain the vault, so those mints are refused as well.
+pub fn reject_unsupported_mint(mint: &AccountInfo) -> Result<()> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(());
+    }
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    if state.get_extension::<TransferFeeConfig>().is_ok() {
+        return err!(ErrorCode::TransferFeeMintUnsupported);
+    }
+    if state.get_extension::<TransferHook>().is_ok()
+        || state.get_extension::<PermanentDelegate>().is_ok()
+    {
+        return err!(ErrorCode::MintExtensionUnsupported);
+    }
+    Ok(())
+}
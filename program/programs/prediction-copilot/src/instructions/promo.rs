@@ -0,0 +1,244 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::instructions::audit::log_admin_action;
+use crate::instructions::multisig::authorize_admin;
+
+#[derive(Accounts)]
+#[instruction(code_hash: [u8; 32])]
+pub struct CreatePromoCode<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = PromoCode::LEN,
+        seeds = [b"promo", code_hash.as_ref()],
+        bump
+    )]
+    pub promo_code: Account<'info, PromoCode>,
+    
+    /// Approved multisig proposal, required when `admin` is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(code_hash: [u8; 32])]
+pub struct DeactivatePromoCode<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"promo", code_hash.as_ref()],
+        bump = promo_code.bump
+    )]
+    pub promo_code: Account<'info, PromoCode>,
+    
+    /// Approved multisig proposal, required when `admin` is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+}
+
+#[event]
+pub struct PromoCodeCreated {
+    pub promo_code: Pubkey,
+    pub discount_kind: u8,
+    pub discount_value: u64,
+    pub allowed_tiers: u64,
+    pub max_redemptions: u32,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct PromoCodeDeactivated {
+    pub promo_code: Pubkey,
+}
+
+#[event]
+pub struct PromoCodeRedeemed {
+    pub promo_code: Pubkey,
+    pub user: Pubkey,
+    pub tier: u8,
+    pub list_price: u64,
+    pub amount: u64,
+    pub mint: Pubkey,
+}
+
+pub fn create_promo_code(
+    ctx: Context<CreatePromoCode>,
+    code_hash: [u8; 32],
+    discount_kind: u8,
+    discount_value: u64,
+    allowed_tiers: u64,
+    max_redemptions: u32,
+    per_wallet_limit: u16,
+    expires_at: i64,
+) -> Result<()> {
+    authorize_admin(
+        &ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &mut ctx.accounts.proposal,
+        AdminAction::CreatePromoCode {
+            code_hash,
+            discount_kind,
+            discount_value,
+            allowed_tiers,
+            max_redemptions,
+            per_wallet_limit,
+            expires_at,
+        },
+    )?;
+    
+    let valid_discount = match discount_kind {
+        PromoCode::DISCOUNT_PERCENT => discount_value > 0 && discount_value <= 10_000,
+        PromoCode::DISCOUNT_FIXED => discount_value > 0,
+        _ => false,
+    };
+    let now = Clock::get()?.unix_timestamp;
+    if !valid_discount
+        || allowed_tiers == 0
+        || max_redemptions == 0
+        || per_wallet_limit == 0
+        || expires_at <= now
+    {
+        return err!(ErrorCode::InvalidPromoCode);
+    }
+    
+    let promo_code = &mut ctx.accounts.promo_code;
+    promo_code.code_hash = code_hash;
+    promo_code.discount_kind = discount_kind;
+    promo_code.discount_value = discount_value;
+    promo_code.allowed_tiers = allowed_tiers;
+    promo_code.max_redemptions = max_redemptions;
+    promo_code.redemptions = 0;
+    promo_code.per_wallet_limit = per_wallet_limit;
+    promo_code.expires_at = expires_at;
+    promo_code.active = true;
+    promo_code.created_by = ctx.accounts.admin.key();
+    promo_code.bump = ctx.bumps.promo_code;
+    
+    emit!(PromoCodeCreated {
+        promo_code: promo_code.key(),
+        discount_kind,
+        discount_value,
+        allowed_tiers,
+        max_redemptions,
+        expires_at,
+    });
+    
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::CreatePromoCode,
+        ctx.accounts.admin.key(),
+        ctx.accounts.promo_code.key(),
+    )?;
+    
+    Ok(())
+}
+
+pub fn deactivate_promo_code(ctx: Context<DeactivatePromoCode>, code_hash: [u8; 32]) -> Result<()> {
+    authorize_admin(
+        &ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &mut ctx.accounts.proposal,
+        AdminAction::DeactivatePromoCode { code_hash },
+    )?;
+    
+    ctx.accounts.promo_code.active = false;
+    
+    emit!(PromoCodeDeactivated {
+        promo_code: ctx.accounts.promo_code.key(),
+    });
+    
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::DeactivatePromoCode,
+        ctx.accounts.admin.key(),
+        ctx.accounts.promo_code.key(),
+    )?;
+    
+    Ok(())
+}
+
+/// Discounted price of a period of `tier` listed at `list_price`. Consumes
+/// one redemption of `promo_code` and records it against `user`.
+pub fn apply_promo_code(
+    config: &ProgramConfig,
+    promo_code: &mut Account<PromoCode>,
+    redemption: &mut PromoRedemption,
+    user: Pubkey,
+    tier: u8,
+    list_price: u64,
+    mint: &Pubkey,
+    now: i64,
+) -> Result<u64> {
+    if !promo_code.active || promo_code.expires_at <= now {
+        return err!(ErrorCode::PromoCodeUnavailable);
+    }
+    if promo_code.allowed_tiers & (1u64 << tier) == 0 {
+        return err!(ErrorCode::PromoCodeUnavailable);
+    }
+    if promo_code.redemptions >= promo_code.max_redemptions {
+        return err!(ErrorCode::PromoCodeUnavailable);
+    }
+    if redemption.count >= promo_code.per_wallet_limit {
+        return err!(ErrorCode::PromoCodeAlreadyRedeemed);
+    }
+    
+    let discount = match promo_code.discount_kind {
+        PromoCode::DISCOUNT_PERCENT => {
+            (list_price as u128 * promo_code.discount_value as u128 / 10_000) as u64
+        }
+        _ => {
+            let decimals = config.payment_mint(mint).map_or(PaymentMint::USD_DECIMALS, |m| m.decimals);
+            PaymentMint::scale_price(promo_code.discount_value, decimals)?
+        }
+    };
+    let price = list_price.saturating_sub(discount);
+    
+    promo_code.redemptions += 1;
+    redemption.promo_code = promo_code.key();
+    redemption.user = user;
+    redemption.count += 1;
+    
+    emit!(PromoCodeRedeemed {
+        promo_code: promo_code.key(),
+        user,
+        tier,
+        list_price,
+        amount: price,
+        mint: *mint,
+    });
+    
+    Ok(price)
+}
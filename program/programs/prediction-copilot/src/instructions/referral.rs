@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = wallet,
+        space = ReferrerProfile::LEN,
+        seeds = [b"referrer", wallet.key().as_ref()],
+        bump
+    )]
+    pub referrer_profile: Account<'info, ReferrerProfile>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct ReferrerRegistered {
+    pub wallet: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+    let profile = &mut ctx.accounts.referrer_profile;
+    let now = Clock::get()?.unix_timestamp;
+    
+    profile.wallet = ctx.accounts.wallet.key();
+    profile.referral_count = 0;
+    profile.total_earned = 0;
+    profile.total_earned_lamports = 0;
+    profile.created_at = now;
+    profile.bump = ctx.bumps.referrer_profile;
+    
+    emit!(ReferrerRegistered {
+        wallet: profile.wallet,
+        timestamp: now,
+    });
+    
+    Ok(())
+}
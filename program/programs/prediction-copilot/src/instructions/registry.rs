@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
+use crate::program::PredictionCopilot;
 use crate::state::*;
 use crate::errors::ErrorCode;
+use crate::instructions::audit::log_admin_action;
+use crate::instructions::multisig::authorize_admin;
 
 #[derive(Accounts)]
 pub struct InitializeRegistry<'info> {
@@ -10,10 +13,14 @@ pub struct InitializeRegistry<'info> {
     #[account(
         seeds = [b"config"],
         bump = config.bump,
-        constraint = config.admin == admin.key() @ ErrorCode::Unauthorized
+        constraint = !config.is_paused(ProgramConfig::PAUSE_REGISTRY) @ ErrorCode::ProgramPaused
     )]
     pub config: Account<'info, ProgramConfig>,
     
+    /// Approved multisig proposal, required when `admin` is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
     #[account(
         init,
         payer = admin,
@@ -23,16 +30,48 @@ pub struct InitializeRegistry<'info> {
     )]
     pub registry: Account<'info, WhaleRegistry>,
     
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ ErrorCode::Unauthorized
+    )]
+    pub program: Program<'info, PredictionCopilot>,
+    
+    /// Only the upgrade authority can initialize, which rules out front-running a fresh deploy
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
     pub system_program: Program<'info, System>,
 }
 
 pub fn initialize_registry(ctx: Context<InitializeRegistry>) -> Result<()> {
+    authorize_admin(
+        &ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &mut ctx.accounts.proposal,
+        AdminAction::InitializeRegistry,
+    )?;
+    
     let registry = &mut ctx.accounts.registry;
     registry.admin = ctx.accounts.admin.key();
     registry.whale_count = 0;
     registry.degen_count = 0;
     registry.last_updated = Clock::get()?.unix_timestamp;
     registry.bump = ctx.bumps.registry;
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::InitializeRegistry,
+        ctx.accounts.admin.key(),
+        ctx.accounts.registry.key(),
+    )?;
+    
     Ok(())
 }
 
@@ -45,10 +84,23 @@ pub struct AddTrader<'info> {
     #[account(
         seeds = [b"config"],
         bump = config.bump,
-        constraint = config.admin == admin.key() @ ErrorCode::Unauthorized
+        constraint = !config.is_paused(ProgramConfig::PAUSE_REGISTRY) @ ErrorCode::ProgramPaused
     )]
     pub config: Account<'info, ProgramConfig>,
     
+    /// Curator role, or else `admin` must be config.admin or pass an approved
+    /// multisig proposal
+    #[account(
+        seeds = [b"role", &[Role::Curator as u8], admin.key().as_ref()],
+        bump = role_grant.bump
+    )]
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    
+    /// Approved multisig proposal, required without `role_grant` when `admin`
+    /// is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
     #[account(
         mut,
         seeds = [b"registry"],
@@ -65,6 +117,13 @@ pub struct AddTrader<'info> {
     )]
     pub trader: Account<'info, TrackedTrader>,
     
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -83,6 +142,23 @@ pub fn add_trader(
     if tier > 4 {
         return err!(ErrorCode::InvalidTraderTier);
     }
+    if ctx.accounts.role_grant.is_none() {
+        authorize_admin(
+            &ctx.accounts.config,
+            ctx.accounts.admin.key(),
+            &mut ctx.accounts.proposal,
+            AdminAction::AddTrader {
+                polygon_address,
+                solana_address,
+                tier,
+                total_pnl,
+                win_rate,
+                trade_count,
+                total_volume,
+                roi,
+            },
+        )?;
+    }
 
     let trader = &mut ctx.accounts.trader;
     let registry = &mut ctx.accounts.registry;
@@ -108,6 +184,13 @@ pub fn add_trader(
     }
     registry.last_updated = now;
     
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::AddTrader,
+        ctx.accounts.admin.key(),
+        ctx.accounts.trader.key(),
+    )?;
+    
     Ok(())
 }
 
@@ -120,10 +203,23 @@ pub struct UpdateTrader<'info> {
     #[account(
         seeds = [b"config"],
         bump = config.bump,
-        constraint = config.admin == admin.key() @ ErrorCode::Unauthorized
+        constraint = !config.is_paused(ProgramConfig::PAUSE_REGISTRY) @ ErrorCode::ProgramPaused
     )]
     pub config: Account<'info, ProgramConfig>,
     
+    /// Curator role, or else `admin` must be config.admin or pass an approved
+    /// multisig proposal
+    #[account(
+        seeds = [b"role", &[Role::Curator as u8], admin.key().as_ref()],
+        bump = role_grant.bump
+    )]
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    
+    /// Approved multisig proposal, required without `role_grant` when `admin`
+    /// is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
     #[account(
         mut,
         seeds = [b"trader", polygon_address.as_ref()],
@@ -137,11 +233,18 @@ pub struct UpdateTrader<'info> {
         bump = registry.bump
     )]
     pub registry: Account<'info, WhaleRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
 }
 
 pub fn update_trader(
     ctx: Context<UpdateTrader>,
-    _polygon_address: [u8; 20],
+    polygon_address: [u8; 20],
     tier: u8,
     total_pnl: i64,
     win_rate: u16,
@@ -153,6 +256,23 @@ pub fn update_trader(
     if tier > 4 {
         return err!(ErrorCode::InvalidTraderTier);
     }
+    if ctx.accounts.role_grant.is_none() {
+        authorize_admin(
+            &ctx.accounts.config,
+            ctx.accounts.admin.key(),
+            &mut ctx.accounts.proposal,
+            AdminAction::UpdateTrader {
+                polygon_address,
+                tier,
+                total_pnl,
+                win_rate,
+                trade_count,
+                total_volume,
+                roi,
+                last_trade_at,
+            },
+        )?;
+    }
 
     let trader = &mut ctx.accounts.trader;
     let registry = &mut ctx.accounts.registry;
@@ -189,6 +309,13 @@ pub fn update_trader(
     
     registry.last_updated = trader.updated_at;
 
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::UpdateTrader,
+        ctx.accounts.admin.key(),
+        ctx.accounts.trader.key(),
+    )?;
+    
     Ok(())
 }
 
@@ -201,10 +328,18 @@ pub struct RemoveTrader<'info> {
     #[account(
         seeds = [b"config"],
         bump = config.bump,
-        constraint = config.admin == admin.key() @ ErrorCode::Unauthorized
+        constraint = !config.is_paused(ProgramConfig::PAUSE_REGISTRY) @ ErrorCode::ProgramPaused
     )]
     pub config: Account<'info, ProgramConfig>,
     
+    /// Curator role, or else `admin` must be config.admin or pass an approved
+    /// multisig proposal
+    #[account(
+        seeds = [b"role", &[Role::Curator as u8], admin.key().as_ref()],
+        bump = role_grant.bump
+    )]
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    
     #[account(
         mut,
         seeds = [b"registry"],
@@ -219,12 +354,31 @@ pub struct RemoveTrader<'info> {
         bump = trader.bump
     )]
     pub trader: Account<'info, TrackedTrader>,
+    
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
 }
 
 pub fn remove_trader(
     ctx: Context<RemoveTrader>,
-    _polygon_address: [u8; 20],
+    polygon_address: [u8; 20],
 ) -> Result<()> {
+    if ctx.accounts.role_grant.is_none() {
+        authorize_admin(
+            &ctx.accounts.config,
+            ctx.accounts.admin.key(),
+            &mut ctx.accounts.proposal,
+            AdminAction::RemoveTrader { polygon_address },
+        )?;
+    }
+    
     let registry = &mut ctx.accounts.registry;
     let trader = &ctx.accounts.trader;
     
@@ -235,5 +389,12 @@ pub fn remove_trader(
     }
     registry.last_updated = Clock::get()?.unix_timestamp;
     
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::RemoveTrader,
+        ctx.accounts.admin.key(),
+        ctx.accounts.trader.key(),
+    )?;
+    
     Ok(())
 }
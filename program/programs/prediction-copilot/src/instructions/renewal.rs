@@ -0,0 +1,395 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Approve, Mint, Revoke, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::instructions::subscription::{
+    credit_subscription, locked_tier_terms, record_paid_period, referral_commission, start_price_lock,
+    ReferralCommissionPaid,
+};
+
+#[derive(Accounts)]
+pub struct EnableAutoRenew<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"subscription", user.key().as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = AutoRenewal::LEN,
+        seeds = [b"auto_renew", user.key().as_ref()],
+        bump
+    )]
+    pub auto_renewal: Account<'info, AutoRenewal>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_SUBSCRIPTIONS) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        constraint = user_usdc.owner == user.key() @ ErrorCode::Unauthorized,
+        constraint = user_usdc.mint == usdc_mint.key() @ ErrorCode::InsufficientBalance
+    )]
+    pub user_usdc: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = config.payment_mint(&usdc_mint.key()).is_some() @ ErrorCode::UnsupportedPaymentMint
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: PDA used only as the SPL token delegate, holds no data
+    #[account(
+        seeds = [b"renewal_delegate"],
+        bump
+    )]
+    pub renewal_delegate: UncheckedAccount<'info>,
+    
+    /// SPL Token or Token-2022, whichever owns `usdc_mint`
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DisableAutoRenew<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"subscription", user.key().as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"auto_renew", user.key().as_ref()],
+        bump = auto_renewal.bump
+    )]
+    pub auto_renewal: Account<'info, AutoRenewal>,
+    
+    #[account(
+        mut,
+        address = auto_renewal.token_account @ ErrorCode::Unauthorized
+    )]
+    pub user_usdc: InterfaceAccount<'info, TokenAccount>,
+    
+    /// SPL Token or Token-2022, whichever owns `user_usdc`
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct RenewSubscription<'info> {
+    /// Permissionless crank, earns the keeper tip
+    pub caller: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"subscription", auto_renewal.user.as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    
+    #[account(
+        mut,
+        seeds = [b"auto_renew", auto_renewal.user.as_ref()],
+        bump = auto_renewal.bump
+    )]
+    pub auto_renewal: Account<'info, AutoRenewal>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_SUBSCRIPTIONS) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        seeds = [b"tier", &[subscription.tier]],
+        bump = tier_config.bump,
+        constraint = tier_config.active @ ErrorCode::TierInactive
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    #[account(
+        mut,
+        address = auto_renewal.token_account @ ErrorCode::Unauthorized
+    )]
+    pub user_usdc: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", usdc_mint.key().as_ref()],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = config,
+        token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Refundable total of `vault`, distribute_revenue holds it back
+    #[account(
+        mut,
+        seeds = [b"vault_ledger", usdc_mint.key().as_ref()],
+        bump = vault_ledger.bump
+    )]
+    pub vault_ledger: Account<'info, VaultLedger>,
+    
+    #[account(
+        mut,
+        token::mint = usdc_mint
+    )]
+    pub caller_usdc: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        address = auto_renewal.mint @ ErrorCode::UnsupportedPaymentMint
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: PDA used only as the SPL token delegate, holds no data
+    #[account(
+        seeds = [b"renewal_delegate"],
+        bump
+    )]
+    pub renewal_delegate: UncheckedAccount<'info>,
+    
+    /// Bound referrer, required when the subscription has one
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer_profile.wallet.as_ref()],
+        bump = referrer_profile.bump
+    )]
+    pub referrer_profile: Option<Account<'info, ReferrerProfile>>,
+    
+    /// Referrer's token account for the payment mint, receives the commission
+    #[account(mut)]
+    pub referrer_usdc: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// SPL Token or Token-2022, whichever owns `usdc_mint`
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event]
+pub struct AutoRenewEnabled {
+    pub user: Pubkey,
+    pub token_account: Pubkey,
+    pub mint: Pubkey,
+    pub max_amount: u64,
+}
+
+#[event]
+pub struct AutoRenewDisabled {
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct SubscriptionRenewed {
+    pub user: Pubkey,
+    pub tier: u8,
+    pub amount: u64,
+    pub keeper_tip: u64,
+    pub caller: Pubkey,
+    pub new_expires_at: i64,
+}
+
+pub fn enable_auto_renew(ctx: Context<EnableAutoRenew>, max_amount: u64) -> Result<()> {
+    if max_amount == 0 {
+        return err!(ErrorCode::InvalidPrice);
+    }
+    
+    // Approve the program PDA to debit at most `max_amount`
+    let cpi_accounts = Approve {
+        to: ctx.accounts.user_usdc.to_account_info(),
+        delegate: ctx.accounts.renewal_delegate.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token_interface::approve(cpi_ctx, max_amount)?;
+    
+    let auto_renewal = &mut ctx.accounts.auto_renewal;
+    auto_renewal.user = ctx.accounts.user.key();
+    auto_renewal.token_account = ctx.accounts.user_usdc.key();
+    auto_renewal.mint = ctx.accounts.usdc_mint.key();
+    auto_renewal.max_amount = max_amount;
+    auto_renewal.bump = ctx.bumps.auto_renewal;
+    ctx.accounts.subscription.auto_renew = true;
+    
+    emit!(AutoRenewEnabled {
+        user: auto_renewal.user,
+        token_account: auto_renewal.token_account,
+        mint: auto_renewal.mint,
+        max_amount,
+    });
+    
+    Ok(())
+}
+
+pub fn disable_auto_renew(ctx: Context<DisableAutoRenew>) -> Result<()> {
+    let cpi_accounts = Revoke {
+        source: ctx.accounts.user_usdc.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token_interface::revoke(cpi_ctx)?;
+    ctx.accounts.subscription.auto_renew = false;
+    
+    emit!(AutoRenewDisabled {
+        user: ctx.accounts.user.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn renew_subscription(ctx: Context<RenewSubscription>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let subscription = &mut ctx.accounts.subscription;
+    let user_usdc = &ctx.accounts.user_usdc;
+    let mint = ctx.accounts.usdc_mint.key();
+    let decimals = ctx.accounts.usdc_mint.decimals;
+    let now = Clock::get()?.unix_timestamp;
+    
+    if subscription.expires_at > now + AutoRenewal::RENEWAL_WINDOW {
+        return err!(ErrorCode::RenewalTooEarly);
+    }
+    // One renewal per due date. Tiers shorter than the window would otherwise
+    // be renewed again and again ahead of time.
+    if now < ctx.accounts.auto_renewal.renewed_due_at {
+        return err!(ErrorCode::RenewalTooEarly);
+    }
+    // A long-lapsed subscription was abandoned, not missed by the crank
+    if subscription.expires_at + AutoRenewal::MAX_LAPSE < now {
+        return err!(ErrorCode::RenewalLapsed);
+    }
+    
+    // Renewals charge the grandfathered price while the lock holds
+    let tier = subscription.tier;
+    let tier_config = &ctx.accounts.tier_config;
+    let (price, duration) = locked_tier_terms(config, subscription, tier_config, &mint, now)?;
+    start_price_lock(config, subscription, tier_config, &mint, now)?;
+    
+    // Fail with a clear error instead of a token program failure
+    if user_usdc.delegate != Some(ctx.accounts.renewal_delegate.key()).into()
+        || user_usdc.delegated_amount < price
+    {
+        return err!(ErrorCode::RenewalAllowanceTooLow);
+    }
+    if user_usdc.amount < price {
+        return err!(ErrorCode::InsufficientBalance);
+    }
+    
+    let user = ctx.accounts.auto_renewal.user;
+    let commission = referral_commission(
+        config,
+        subscription,
+        user,
+        &mint,
+        price,
+        ctx.accounts.referrer_profile.as_mut(),
+        ctx.accounts.referrer_usdc.as_ref().map(|account| (account.owner, account.mint)),
+    )?;
+    
+    let keeper_tip = price * AutoRenewal::KEEPER_TIP_BPS / 10_000;
+    let delegate_seeds: &[&[u8]] = &[b"renewal_delegate", &[ctx.bumps.renewal_delegate]];
+    let signer_seeds = &[delegate_seeds];
+    
+    let cpi_accounts = TransferChecked {
+        from: user_usdc.to_account_info(),
+        mint: ctx.accounts.usdc_mint.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.renewal_delegate.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token_interface::transfer_checked(cpi_ctx, price - keeper_tip - commission, decimals)?;
+    
+    if keeper_tip > 0 {
+        let cpi_accounts = TransferChecked {
+            from: user_usdc.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
+            to: ctx.accounts.caller_usdc.to_account_info(),
+            authority: ctx.accounts.renewal_delegate.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_interface::transfer_checked(cpi_ctx, keeper_tip, decimals)?;
+    }
+    
+    if let (Some(profile), Some(referrer_usdc)) = (&ctx.accounts.referrer_profile, &ctx.accounts.referrer_usdc) {
+        if commission > 0 {
+            let cpi_accounts = TransferChecked {
+                from: user_usdc.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+                to: referrer_usdc.to_account_info(),
+                authority: ctx.accounts.renewal_delegate.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            token_interface::transfer_checked(cpi_ctx, commission, decimals)?;
+            
+            emit!(ReferralCommissionPaid {
+                referrer: profile.wallet,
+                user,
+                amount: commission,
+                mint,
+            });
+        }
+    }
+    
+    // The keeper tip and commission never reach the vault, so neither is refundable
+    let due_at = subscription.expires_at;
+    let bump = subscription.bump;
+    let new_expires_at = credit_subscription(
+        subscription,
+        user,
+        tier,
+        price - keeper_tip - commission,
+        duration,
+        mint,
+        bump,
+        now,
+    )?;
+    record_paid_period(
+        subscription,
+        &mut ctx.accounts.vault_ledger,
+        price - keeper_tip - commission,
+        new_expires_at - duration,
+        new_expires_at,
+        now,
+    )?;
+    
+    let auto_renewal = &mut ctx.accounts.auto_renewal;
+    auto_renewal.renewals += 1;
+    auto_renewal.last_renewed_at = now;
+    auto_renewal.renewed_due_at = due_at;
+    
+    emit!(SubscriptionRenewed {
+        user,
+        tier,
+        amount: price,
+        keeper_tip,
+        caller: ctx.accounts.caller.key(),
+        new_expires_at,
+    });
+    
+    Ok(())
+}
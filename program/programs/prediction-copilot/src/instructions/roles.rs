@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::instructions::audit::log_admin_action;
+use crate::instructions::multisig::authorize_admin;
+
+#[derive(Accounts)]
+#[instruction(role: u8, holder: Pubkey)]
+pub struct GrantRole<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = RoleGrant::LEN,
+        seeds = [b"role", role.to_le_bytes().as_ref(), holder.as_ref()],
+        bump
+    )]
+    pub role_grant: Account<'info, RoleGrant>,
+    
+    /// Approved multisig proposal, required when `admin` is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(role: u8, holder: Pubkey)]
+pub struct RevokeRole<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"role", role.to_le_bytes().as_ref(), holder.as_ref()],
+        bump = role_grant.bump
+    )]
+    pub role_grant: Account<'info, RoleGrant>,
+    
+    /// Approved multisig proposal, required when `admin` is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+}
+
+#[event]
+pub struct RoleGranted {
+    pub role: u8,
+    pub holder: Pubkey,
+    pub granted_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub role: u8,
+    pub holder: Pubkey,
+    pub revoked_by: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn grant_role(ctx: Context<GrantRole>, role: u8, holder: Pubkey) -> Result<()> {
+    // Admin is not a grantable role, it moves through the handover flow
+    if role == Role::Admin as u8 || role > Role::Curator as u8 {
+        return err!(ErrorCode::InvalidRole);
+    }
+    
+    authorize_admin(
+        &ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &mut ctx.accounts.proposal,
+        AdminAction::GrantRole { role, holder },
+    )?;
+    
+    let role_grant = &mut ctx.accounts.role_grant;
+    let now = Clock::get()?.unix_timestamp;
+    
+    role_grant.holder = holder;
+    role_grant.role = role;
+    role_grant.granted_by = ctx.accounts.admin.key();
+    role_grant.granted_at = now;
+    role_grant.bump = ctx.bumps.role_grant;
+    
+    emit!(RoleGranted {
+        role,
+        holder,
+        granted_by: role_grant.granted_by,
+        timestamp: now,
+    });
+    
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::GrantRole,
+        ctx.accounts.admin.key(),
+        holder,
+    )?;
+    
+    Ok(())
+}
+
+pub fn revoke_role(ctx: Context<RevokeRole>, role: u8, holder: Pubkey) -> Result<()> {
+    authorize_admin(
+        &ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &mut ctx.accounts.proposal,
+        AdminAction::RevokeRole { role, holder },
+    )?;
+    
+    emit!(RoleRevoked {
+        role,
+        holder,
+        revoked_by: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::RevokeRole,
+        ctx.accounts.admin.key(),
+        holder,
+    )?;
+    
+    Ok(())
+}
@@ -11,10 +11,16 @@ pub struct PublishSignal<'info> {
     #[account(
         seeds = [b"config"],
         bump = config.bump,
-        constraint = config.admin == publisher.key() @ ErrorCode::Unauthorized
+        constraint = !config.is_paused(ProgramConfig::PAUSE_SIGNALS) @ ErrorCode::ProgramPaused
     )]
     pub config: Account<'info, ProgramConfig>,
     
+    #[account(
+        seeds = [b"role", &[Role::Publisher as u8], publisher.key().as_ref()],
+        bump = role_grant.bump
+    )]
+    pub role_grant: Account<'info, RoleGrant>,
+    
     #[account(
         init,
         payer = publisher,
@@ -30,6 +36,16 @@ pub struct PublishSignal<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[event]
+pub struct SignalPublished {
+    pub signal: Pubkey,
+    pub asset: [u8; 16],
+    pub publisher: Pubkey,
+    pub multiplier: u16,
+    pub severity: u8,
+    pub detected_at: i64,
+}
+
 pub fn publish_signal(
     ctx: Context<PublishSignal>,
     asset: [u8; 16],
@@ -59,7 +75,16 @@ pub fn publish_signal(
     signal.publisher = ctx.accounts.publisher.key();
     signal.bump = ctx.bumps.signal;
     
-    // Emit event (to be implemented if needed)
+    // Signals are routine publisher output, indexers follow them through
+    // this event rather than the admin log
+    emit!(SignalPublished {
+        signal: signal.key(),
+        asset,
+        publisher: signal.publisher,
+        multiplier,
+        severity,
+        detected_at,
+    });
     
     Ok(())
 }
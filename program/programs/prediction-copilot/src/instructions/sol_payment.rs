@@ -0,0 +1,186 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_interface::TokenAccount;
+use pyth_solana_receiver_sdk::price_update::{get_feed_id_from_hex, PriceUpdateV2};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::instructions::subscription::{
+    credit_subscription, locked_usd_price, referral_commission, start_price_lock, ReferralCommissionPaid,
+};
+
+#[derive(Accounts)]
+#[instruction(tier: u8)]
+pub struct SubscribeWithSol<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = Subscription::LEN,
+        seeds = [b"subscription", user.key().as_ref()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_SUBSCRIPTIONS) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        seeds = [b"tier", &[tier]],
+        bump = tier_config.bump,
+        constraint = tier_config.active @ ErrorCode::TierInactive
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    /// SOL/USD price update posted by the Pyth receiver
+    pub price_update: Account<'info, PriceUpdateV2>,
+    
+    #[account(address = config.treasury @ ErrorCode::Unauthorized)]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Wallet owning the treasury token account, receives the lamports
+    #[account(
+        mut,
+        address = treasury.owner @ ErrorCode::Unauthorized
+    )]
+    pub treasury_wallet: SystemAccount<'info>,
+    
+    /// Bound referrer, required when the subscription has one
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer_profile.wallet.as_ref()],
+        bump = referrer_profile.bump
+    )]
+    pub referrer_profile: Option<Account<'info, ReferrerProfile>>,
+    
+    /// Referrer wallet, receives the commission in lamports
+    #[account(mut)]
+    pub referrer_wallet: Option<SystemAccount<'info>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct SolPaymentReceived {
+    pub user: Pubkey,
+    pub tier: u8,
+    pub usd_price: u64,
+    pub lamports: u64,
+    pub sol_price: i64,
+    pub sol_price_exponent: i32,
+}
+
+pub fn subscribe_with_sol(
+    ctx: Context<SubscribeWithSol>,
+    tier: u8,
+    max_lamports: u64,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let subscription = &mut ctx.accounts.subscription;
+    let tier_config = &ctx.accounts.tier_config;
+    let now = Clock::get()?.unix_timestamp;
+    
+    // Existing subscribers keep paying their locked price
+    let usd_price = locked_usd_price(config, subscription, tier_config, now);
+    let duration = tier_config.duration;
+    start_price_lock(config, subscription, tier_config, &native_mint::ID, now)?;
+    
+    // 1. Read a fresh, tight SOL/USD price
+    let feed_id = get_feed_id_from_hex(ProgramConfig::SOL_USD_FEED_ID)?;
+    let price = ctx.accounts.price_update.get_price_no_older_than(
+        &Clock::get()?,
+        ProgramConfig::MAX_SOL_PRICE_AGE,
+        &feed_id,
+    )?;
+    if price.price <= 0
+        || price.conf as u128 * 10_000
+            > price.price as u128 * ProgramConfig::MAX_SOL_PRICE_CONF_BPS as u128
+    {
+        return err!(ErrorCode::PriceTooUncertain);
+    }
+    
+    // 2. Convert USD (6 decimals) to lamports (9 decimals), rounding up.
+    // lamports = usd_price * 10^(9 - 6) / (price * 10^exponent)
+    let shift = 3 - price.exponent;
+    let (numerator, denominator) = if shift >= 0 {
+        let scale = 10u128.checked_pow(shift as u32).ok_or(ErrorCode::PriceTooUncertain)?;
+        (usd_price as u128 * scale, price.price as u128)
+    } else {
+        let scale = 10u128.checked_pow((-shift) as u32).ok_or(ErrorCode::PriceTooUncertain)?;
+        (usd_price as u128, price.price as u128 * scale)
+    };
+    let lamports = u64::try_from((numerator + denominator - 1) / denominator)
+        .map_err(|_| ErrorCode::PriceTooUncertain)?;
+    if lamports > max_lamports {
+        return err!(ErrorCode::SlippageExceeded);
+    }
+    
+    // 3. Pay the treasury in SOL, less the bound referrer's commission
+    let user = ctx.accounts.user.key();
+    let commission = referral_commission(
+        config,
+        subscription,
+        user,
+        &native_mint::ID,
+        lamports,
+        ctx.accounts.referrer_profile.as_mut(),
+        ctx.accounts.referrer_wallet.as_ref().map(|wallet| (wallet.key(), native_mint::ID)),
+    )?;
+    
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.user.to_account_info(),
+        to: ctx.accounts.treasury_wallet.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.system_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    system_program::transfer(cpi_ctx, lamports - commission)?;
+    
+    if let (Some(profile), Some(referrer_wallet)) = (&ctx.accounts.referrer_profile, &ctx.accounts.referrer_wallet) {
+        if commission > 0 {
+            let cpi_accounts = system_program::Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: referrer_wallet.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.system_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            system_program::transfer(cpi_ctx, commission)?;
+            
+            emit!(ReferralCommissionPaid {
+                referrer: profile.wallet,
+                user,
+                amount: commission,
+                mint: native_mint::ID,
+            });
+        }
+    }
+    
+    // 4. Update subscription. The lamports sit in the treasury wallet, out of
+    // reach of cancel_subscription, so this period is not refundable.
+    credit_subscription(
+        subscription,
+        user,
+        tier,
+        lamports - commission,
+        duration,
+        native_mint::ID,
+        ctx.bumps.subscription,
+        now,
+    )?;
+    
+    emit!(SolPaymentReceived {
+        user,
+        tier,
+        usd_price,
+        lamports,
+        sol_price: price.price,
+        sol_price_exponent: price.exponent,
+    });
+    
+    Ok(())
+}
@@ -1,9 +1,13 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer, Mint};
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_interface::{self, Mint, Revoke, TokenAccount, TokenInterface, TransferChecked};
 use crate::state::*;
 use crate::errors::ErrorCode;
+use crate::instructions::promo::apply_promo_code;
+use crate::instructions::renewal::AutoRenewDisabled;
 
 #[derive(Accounts)]
+#[instruction(tier: u8)]
 pub struct Subscribe<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
@@ -20,63 +24,312 @@ pub struct Subscribe<'info> {
     #[account(
         seeds = [b"config"],
         bump = config.bump,
-        constraint = !config.paused @ ErrorCode::ProgramPaused
+        constraint = !config.is_paused(ProgramConfig::PAUSE_SUBSCRIPTIONS) @ ErrorCode::ProgramPaused
     )]
     pub config: Account<'info, ProgramConfig>,
     
+    #[account(
+        seeds = [b"tier", &[tier]],
+        bump = tier_config.bump,
+        constraint = tier_config.active @ ErrorCode::TierInactive
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
     #[account(
         mut,
         constraint = user_usdc.owner == user.key() @ ErrorCode::Unauthorized,
         constraint = user_usdc.mint == usdc_mint.key() @ ErrorCode::InsufficientBalance
     )]
-    pub user_usdc: Account<'info, TokenAccount>,
+    pub user_usdc: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
-        constraint = treasury_usdc.owner == config.treasury @ ErrorCode::Unauthorized,
-        constraint = treasury_usdc.mint == usdc_mint.key() @ ErrorCode::Unauthorized
+        seeds = [b"vault", usdc_mint.key().as_ref()],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = config,
+        token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = config.payment_mint(&usdc_mint.key()).is_some() @ ErrorCode::UnsupportedPaymentMint
     )]
-    pub treasury_usdc: Account<'info, TokenAccount>,
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
     
-    pub usdc_mint: Account<'info, Mint>,
-    pub token_program: Program<'info, Token>,
+    /// Refundable total of `vault`, distribute_revenue holds it back
+    #[account(
+        mut,
+        seeds = [b"vault_ledger", usdc_mint.key().as_ref()],
+        bump = vault_ledger.bump
+    )]
+    pub vault_ledger: Account<'info, VaultLedger>,
+    
+    /// Referrer, passed together with `referrer_usdc`. Required on every
+    /// payment once the subscription has a referrer.
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer_profile.wallet.as_ref()],
+        bump = referrer_profile.bump
+    )]
+    pub referrer_profile: Option<Account<'info, ReferrerProfile>>,
+    
+    /// Referrer's token account for the payment mint, receives the commission
+    #[account(mut)]
+    pub referrer_usdc: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Optional promo code, must be passed together with `promo_redemption`
+    #[account(
+        mut,
+        seeds = [b"promo", promo_code.code_hash.as_ref()],
+        bump = promo_code.bump
+    )]
+    pub promo_code: Option<Account<'info, PromoCode>>,
+    
+    /// This wallet's redemption record for `promo_code`
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = PromoRedemption::LEN,
+        seeds = [
+            b"promo_redemption",
+            promo_code.as_ref().map(|promo_code| promo_code.key()).unwrap_or_default().as_ref(),
+            user.key().as_ref()
+        ],
+        bump
+    )]
+    pub promo_redemption: Option<Account<'info, PromoRedemption>>,
+    
+    /// SPL Token or Token-2022, whichever owns `usdc_mint`
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
+#[event]
+pub struct ReferralCommissionPaid {
+    pub referrer: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub mint: Pubkey,
+}
+
+#[event]
+pub struct SubscriptionCreated {
+    pub user: Pubkey,
+    pub tier: u8,
+    pub amount: u64,
+    pub mint: Pubkey,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct SubscriptionExtended {
+    pub user: Pubkey,
+    pub tier: u8,
+    pub amount: u64,
+    pub mint: Pubkey,
+    pub new_expires_at: i64,
+}
+
 pub fn subscribe(ctx: Context<Subscribe>, tier: u8) -> Result<()> {
     let config = &ctx.accounts.config;
     let subscription = &mut ctx.accounts.subscription;
     
-    // 1. Validate tier
-    let (price, duration) = match tier {
-        1 => (config.basic_price, config.basic_duration),
-        2 => (config.pro_price, config.pro_duration),
-        _ => return err!(ErrorCode::InvalidTier),
+    let user = ctx.accounts.user.key();
+    let mint = ctx.accounts.usdc_mint.key();
+    let decimals = ctx.accounts.usdc_mint.decimals;
+    
+    let now = Clock::get()?.unix_timestamp;
+    
+    // 1. Validate tier, priced in the payment mint's units. Existing
+    // subscribers keep paying their locked price.
+    let tier_config = &ctx.accounts.tier_config;
+    let (list_price, duration) = locked_tier_terms(config, subscription, tier_config, &mint, now)?;
+    start_price_lock(config, subscription, tier_config, &mint, now)?;
+    
+    // 2. Apply the promo code, if any, to the (possibly grandfathered) price
+    let price = match (&mut ctx.accounts.promo_code, &mut ctx.accounts.promo_redemption) {
+        (Some(promo_code), Some(redemption)) => {
+            redemption.bump = ctx.bumps.promo_redemption;
+            apply_promo_code(config, promo_code, redemption, user, tier, list_price, &mint, now)?
+        }
+        (None, None) => list_price,
+        _ => return err!(ErrorCode::InvalidPromoCode),
     };
     
-    // 2. Transfer USDC
-    let cpi_accounts = Transfer {
+    // 3. Resolve the referrer. The first paid subscription binds it for good,
+    // every later payment pays commission to that same referrer.
+    let commission = referral_commission(
+        config,
+        subscription,
+        user,
+        &mint,
+        price,
+        ctx.accounts.referrer_profile.as_mut(),
+        ctx.accounts.referrer_usdc.as_ref().map(|account| (account.owner, account.mint)),
+    )?;
+    
+    // 4. Transfer payment into the program vault, less any commission.
+    // Fee-bearing Token-2022 mints are rejected in add_payment_mint, so the
+    // vault receives exactly what is credited.
+    let cpi_accounts = TransferChecked {
         from: ctx.accounts.user_usdc.to_account_info(),
-        to: ctx.accounts.treasury_usdc.to_account_info(),
+        mint: ctx.accounts.usdc_mint.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
         authority: ctx.accounts.user.to_account_info(),
     };
     
     let cpi_program = ctx.accounts.token_program.to_account_info();
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-    token::transfer(cpi_ctx, price)?;
+    if price - commission > 0 {
+        token_interface::transfer_checked(cpi_ctx, price - commission, decimals)?;
+    }
     
-    // 3. Update subscription
-    let now = Clock::get()?.unix_timestamp;
+    if let (Some(profile), Some(referrer_usdc)) = (&ctx.accounts.referrer_profile, &ctx.accounts.referrer_usdc) {
+        if commission > 0 {
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.user_usdc.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+                to: referrer_usdc.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            token_interface::transfer_checked(cpi_ctx, commission, decimals)?;
+            
+            emit!(ReferralCommissionPaid {
+                referrer: profile.wallet,
+                user,
+                amount: commission,
+                mint,
+            });
+        }
+    }
+    
+    // 5. Update subscription. Only the vault's share is refundable, the
+    // commission has already left the program.
+    let expires_at = credit_subscription(
+        &mut ctx.accounts.subscription,
+        user,
+        tier,
+        price - commission,
+        duration,
+        mint,
+        ctx.bumps.subscription,
+        now,
+    )?;
+    record_paid_period(
+        &mut ctx.accounts.subscription,
+        &mut ctx.accounts.vault_ledger,
+        price - commission,
+        expires_at - duration,
+        expires_at,
+        now,
+    )?;
+    
+    Ok(())
+}
+
+/// Commission owed to the referrer on a payment of `price`. Binds `profile`
+/// on the first payment, and requires the bound referrer's accounts on every
+/// later payment so the commission cannot be skipped. `payout` is the owner
+/// and mint of the account receiving the commission, the native mint for SOL.
+pub fn referral_commission(
+    config: &ProgramConfig,
+    subscription: &mut Subscription,
+    user: Pubkey,
+    mint: &Pubkey,
+    price: u64,
+    referrer_profile: Option<&mut Account<ReferrerProfile>>,
+    payout: Option<(Pubkey, Pubkey)>,
+) -> Result<u64> {
+    // Trials, gifts and passes are unpaid, so they leave the referrer open.
+    // Subscriptions migrated from the first layout only carry total_paid.
+    let may_bind = subscription.last_payment_at == 0 && subscription.total_paid == 0;
+    let (commission, referrer) = bound_referral_commission(
+        config,
+        subscription.referrer,
+        may_bind,
+        user,
+        mint,
+        price,
+        referrer_profile,
+        payout,
+    )?;
+    if may_bind {
+        subscription.referrer = referrer;
+    }
+    Ok(commission)
+}
+
+/// Commission owed on a payment of `price` by a wallet bound to `bound`.
+/// A referrer can only be named while nothing is bound if `may_bind`.
+/// Returns the commission and the referrer it goes to.
+pub fn bound_referral_commission(
+    config: &ProgramConfig,
+    bound: Option<Pubkey>,
+    may_bind: bool,
+    user: Pubkey,
+    mint: &Pubkey,
+    price: u64,
+    referrer_profile: Option<&mut Account<ReferrerProfile>>,
+    payout: Option<(Pubkey, Pubkey)>,
+) -> Result<(u64, Option<Pubkey>)> {
+    let (profile, (payout_owner, payout_mint)) = match (referrer_profile, payout) {
+        (Some(profile), Some(payout)) => (profile, payout),
+        (None, None) if bound.is_none() => return Ok((0, None)),
+        _ => return err!(ErrorCode::InvalidReferrer),
+    };
+    if profile.wallet == user || payout_owner != profile.wallet || payout_mint != *mint {
+        return err!(ErrorCode::InvalidReferrer);
+    }
+    match bound {
+        Some(referrer) if referrer == profile.wallet => {}
+        None if may_bind => profile.referral_count += 1,
+        _ => return err!(ErrorCode::InvalidReferrer),
+    }
     
+    let commission = (price as u128 * config.referral_commission_bps as u128 / 10_000) as u64;
+    if *mint == native_mint::ID {
+        profile.total_earned_lamports += commission;
+    } else {
+        profile.total_earned += commission;
+    }
+    Ok((commission, Some(profile.wallet)))
+}
+
+/// Applies a period of `tier` to `subscription` and emits
+/// `SubscriptionCreated` or `SubscriptionExtended`. Returns the new expiry.
+/// `amount` is what the vault received from the subscriber, token payments
+/// also go through `record_paid_period` to become refundable. SOL payments go
+/// straight to the treasury wallet, so they are tallied in lamports and never
+/// refunded.
+pub fn credit_subscription(
+    subscription: &mut Subscription,
+    user: Pubkey,
+    tier: u8,
+    amount: u64,
+    duration: i64,
+    mint: Pubkey,
+    bump: u8,
+    now: i64,
+) -> Result<i64> {
     // Calculate new expiry
     let current_expiry = subscription.expires_at;
-    let new_expiry = if current_expiry > now {
+    let is_extension = current_expiry > now;
+    
+    // Switching tiers on an active subscription must be prorated
+    if is_extension && subscription.tier != tier {
+        return err!(ErrorCode::UseChangeTier);
+    }
+    let new_expiry = if is_extension {
         current_expiry + duration
     } else {
         now + duration
     };
     
-    subscription.user = ctx.accounts.user.key();
+    let is_sol = mint == native_mint::ID;
+    subscription.user = user;
     subscription.tier = tier;
     
     // Only set started_at if it's new or expired
@@ -85,11 +338,564 @@ pub fn subscribe(ctx: Context<Subscribe>, tier: u8) -> Result<()> {
     }
     
     subscription.expires_at = new_expiry;
-    subscription.total_paid += price;
-    subscription.bump = ctx.bumps.subscription;
+    if is_sol {
+        subscription.total_paid_lamports += amount;
+    } else {
+        subscription.total_paid += amount;
+    }
+    if amount > 0 {
+        subscription.last_payment_at = now;
+    }
+    subscription.bump = bump;
+    subscription.is_trial = false;
     
-    // Emit event? (Spec mentions SubscriptionCreated/Extended, will implement later or now if easy)
-    // For now skipping events file to save context window, will add if needed.
+    // A lapsed subscription that is renewed counts as a new one
+    if is_extension {
+        emit!(SubscriptionExtended {
+            user,
+            tier,
+            amount,
+            mint,
+            new_expires_at: new_expiry,
+        });
+    } else {
+        emit!(SubscriptionCreated {
+            user,
+            tier,
+            amount,
+            mint,
+            expires_at: new_expiry,
+        });
+    }
+    
+    Ok(new_expiry)
+}
+
+/// Records a payment of `amount` into `ledger`'s vault for the time from
+/// `starts_at` to `ends_at` as a refundable period. Refunds never pay out
+/// more than that.
+pub fn record_paid_period(
+    subscription: &mut Subscription,
+    ledger: &mut VaultLedger,
+    amount: u64,
+    starts_at: i64,
+    ends_at: i64,
+    now: i64,
+) -> Result<()> {
+    prune_expired_periods(subscription, ledger, now);
+    if amount == 0 {
+        return Ok(());
+    }
+    if subscription.refundable_mint(now).is_some_and(|paid_mint| paid_mint != ledger.mint) {
+        return err!(ErrorCode::PaymentMintMismatch);
+    }
     
+    push_paid_period(
+        subscription,
+        ledger,
+        PaidPeriod {
+            mint: ledger.mint,
+            amount,
+            paid_at: now,
+            starts_at,
+            ends_at,
+        },
+    )
+}
+
+/// Appends `period` and adds its amount to `ledger`'s refundable total.
+fn push_paid_period(subscription: &mut Subscription, ledger: &mut VaultLedger, period: PaidPeriod) -> Result<()> {
+    if subscription.paid_periods.len() >= Subscription::MAX_PAID_PERIODS {
+        return err!(ErrorCode::TooManyPaidPeriods);
+    }
+    ledger.refundable = ledger.refundable.checked_add(period.amount).ok_or(ErrorCode::MathOverflow)?;
+    subscription.paid_periods.push(period);
     Ok(())
 }
+
+/// Drops the periods in `ledger`'s mint that ended by `now`. Their cash can
+/// no longer be refunded, so it becomes distributable revenue.
+pub fn prune_expired_periods(subscription: &mut Subscription, ledger: &mut VaultLedger, now: i64) {
+    let mint = ledger.mint;
+    let mut released = 0u64;
+    subscription.paid_periods.retain(|period| {
+        let expired = period.mint == mint && period.ends_at <= now;
+        if expired {
+            released += period.amount;
+        }
+        !expired
+    });
+    ledger.refundable = ledger.refundable.saturating_sub(released);
+}
+
+/// Drops every period in `ledger`'s mint, returning their total amount.
+fn release_all_periods(subscription: &mut Subscription, ledger: &mut VaultLedger) -> u64 {
+    let mint = ledger.mint;
+    let mut released = 0u64;
+    subscription.paid_periods.retain(|period| {
+        if period.mint == mint {
+            released += period.amount;
+        }
+        period.mint != mint
+    });
+    ledger.refundable = ledger.refundable.saturating_sub(released);
+    released
+}
+
+#[derive(Accounts)]
+#[instruction(new_tier: u8)]
+pub struct ChangeTier<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"subscription", user.key().as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_SUBSCRIPTIONS) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// Current tier, may already be retired
+    #[account(
+        seeds = [b"tier", &[subscription.tier]],
+        bump = old_tier_config.bump
+    )]
+    pub old_tier_config: Account<'info, TierConfig>,
+    
+    #[account(
+        seeds = [b"tier", &[new_tier]],
+        bump = new_tier_config.bump,
+        constraint = new_tier_config.active @ ErrorCode::TierInactive
+    )]
+    pub new_tier_config: Account<'info, TierConfig>,
+    
+    #[account(
+        mut,
+        constraint = user_usdc.owner == user.key() @ ErrorCode::Unauthorized,
+        constraint = user_usdc.mint == usdc_mint.key() @ ErrorCode::InsufficientBalance
+    )]
+    pub user_usdc: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", usdc_mint.key().as_ref()],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = config,
+        token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = config.payment_mint(&usdc_mint.key()).is_some() @ ErrorCode::UnsupportedPaymentMint
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Refundable total of `vault`, distribute_revenue holds it back
+    #[account(
+        mut,
+        seeds = [b"vault_ledger", usdc_mint.key().as_ref()],
+        bump = vault_ledger.bump
+    )]
+    pub vault_ledger: Account<'info, VaultLedger>,
+    /// SPL Token or Token-2022, whichever owns `usdc_mint`
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event]
+pub struct TierChanged {
+    pub user: Pubkey,
+    pub old_tier: u8,
+    pub new_tier: u8,
+    pub credit: u64,
+    pub amount_charged: u64,
+    pub mint: Pubkey,
+    pub new_expires_at: i64,
+}
+
+pub fn change_tier(ctx: Context<ChangeTier>, new_tier: u8) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let subscription = &mut ctx.accounts.subscription;
+    let mint = ctx.accounts.usdc_mint.key();
+    let now = Clock::get()?.unix_timestamp;
+    
+    if subscription.expires_at <= now {
+        return err!(ErrorCode::SubscriptionExpired);
+    }
+    let old_tier = subscription.tier;
+    if old_tier == new_tier {
+        return err!(ErrorCode::InvalidTier);
+    }
+    
+    let (old_price, old_duration) =
+        locked_tier_terms(config, subscription, &ctx.accounts.old_tier_config, &mint, now)?;
+    let (new_price, new_duration) = tier_terms(config, &ctx.accounts.new_tier_config, &mint)?;
+    
+    // Only cash actually paid for the unused time is credited, so a promo
+    // discount cannot be cashed out at list price. Unpaid time (trial, gift,
+    // pass) carries no credit. The cash moves onto the new tier, keeping
+    // refunds bounded by what was paid and in the same mint.
+    let credit = subscription.unused_paid(now);
+    
+    // Tier ids carry no ordering, a tier is an upgrade if it costs more per second
+    let is_upgrade = new_price as u128 * old_duration as u128 > old_price as u128 * new_duration as u128;
+    let (amount_charged, new_expiry) = if is_upgrade && credit < new_price {
+        // Upgrade: credit goes towards a full period at the new tier
+        (new_price - credit, now + new_duration)
+    } else {
+        // Downgrade, or credit worth a full period or more: it all converts
+        // into time at the new tier's rate, nothing is thrown away
+        let extended = credit as u128 * new_duration as u128 / new_price as u128;
+        (0, now + extended as i64)
+    };
+    
+    if (credit > 0 || amount_charged > 0)
+        && subscription.refundable_mint(now).is_some_and(|paid_mint| paid_mint != mint)
+    {
+        return err!(ErrorCode::PaymentMintMismatch);
+    }
+    
+    if amount_charged > 0 {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.user_usdc.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token_interface::transfer_checked(cpi_ctx, amount_charged, ctx.accounts.usdc_mint.decimals)?;
+    }
+    
+    // The new tier is a fresh commitment, so its price is locked anew
+    reset_price_lock(config, subscription, &ctx.accounts.new_tier_config, &mint, now)?;
+    subscription.tier = new_tier;
+    subscription.expires_at = new_expiry;
+    subscription.is_trial = false;
+    subscription.total_paid += amount_charged;
+    if amount_charged > 0 {
+        subscription.last_payment_at = now;
+    }
+    // The carried cash keeps its original payment date, so changing tiers
+    // does not reopen the fee-free refund window. Only the new charge is
+    // dated now. The credit pays for the start of the new term.
+    let carried_paid_at = subscription
+        .paid_periods
+        .iter()
+        .filter(|period| period.ends_at > now)
+        .map(|period| period.paid_at)
+        .max()
+        .unwrap_or(now);
+    let carried_until = if amount_charged > 0 {
+        let carried = (new_expiry - now) as u128 * credit as u128 / (credit + amount_charged) as u128;
+        now + carried as i64
+    } else {
+        new_expiry
+    };
+    let ledger = &mut ctx.accounts.vault_ledger;
+    release_all_periods(subscription, ledger);
+    let periods = [
+        (credit, carried_paid_at, now, carried_until),
+        (amount_charged, now, carried_until, new_expiry),
+    ];
+    for (amount, paid_at, starts_at, ends_at) in periods {
+        if amount > 0 {
+            push_paid_period(subscription, ledger, PaidPeriod { mint, amount, paid_at, starts_at, ends_at })?;
+        }
+    }
+    
+    emit!(TierChanged {
+        user: ctx.accounts.user.key(),
+        old_tier,
+        new_tier,
+        credit,
+        amount_charged,
+        mint,
+        new_expires_at: new_expiry,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelSubscription<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"subscription", user.key().as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_SUBSCRIPTIONS) @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        constraint = user_usdc.owner == user.key() @ ErrorCode::Unauthorized,
+        constraint = user_usdc.mint == usdc_mint.key() @ ErrorCode::InsufficientBalance
+    )]
+    pub user_usdc: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", usdc_mint.key().as_ref()],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = config,
+        token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Mint the running periods were paid in, may since have been removed
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Refundable total of `vault`, distribute_revenue holds it back
+    #[account(
+        mut,
+        seeds = [b"vault_ledger", usdc_mint.key().as_ref()],
+        bump = vault_ledger.bump
+    )]
+    pub vault_ledger: Account<'info, VaultLedger>,
+    
+    /// Closed on cancel, required while `subscription.auto_renew` is set
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"auto_renew", user.key().as_ref()],
+        bump = auto_renewal.bump
+    )]
+    pub auto_renewal: Option<Account<'info, AutoRenewal>>,
+    /// SPL Token or Token-2022, whichever owns `usdc_mint`
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event]
+pub struct SubscriptionCancelled {
+    pub user: Pubkey,
+    pub tier: u8,
+    pub refund: u64,
+    pub fee: u64,
+    pub mint: Pubkey,
+    pub cancelled_at: i64,
+}
+
+pub fn cancel_subscription(ctx: Context<CancelSubscription>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let subscription = &mut ctx.accounts.subscription;
+    let mint = ctx.accounts.usdc_mint.key();
+    let now = Clock::get()?.unix_timestamp;
+    
+    if subscription.expires_at <= now {
+        return err!(ErrorCode::SubscriptionExpired);
+    }
+    // A trial was never paid for, it simply runs out
+    if subscription.is_trial {
+        return err!(ErrorCode::TrialNotRefundable);
+    }
+    // Otherwise the crank would renew the subscription right away
+    if subscription.auto_renew && ctx.accounts.auto_renewal.is_none() {
+        return err!(ErrorCode::AutoRenewalRequired);
+    }
+    
+    // Only paid periods are refundable, each in its own mint and never for
+    // more than it cost. Inside the refund window the latest payment is
+    // refunded in full and fee-free, earlier periods only for unused time.
+    let latest = subscription.paid_periods.len().saturating_sub(1);
+    let mut gross = 0u64;
+    let mut fee = 0u64;
+    for (index, period) in subscription.paid_periods.iter().enumerate() {
+        if period.ends_at <= now {
+            continue;
+        }
+        if period.mint != mint {
+            return err!(ErrorCode::PaymentMintMismatch);
+        }
+        if index == latest && now - period.paid_at < config.refund_window {
+            gross += period.amount;
+        } else {
+            let prorated = period.unused_amount(now);
+            gross += prorated;
+            fee += (prorated as u128 * config.cancellation_fee_bps as u128 / 10_000) as u64;
+        }
+    }
+    let refund = gross - fee;
+    
+    if refund > 0 {
+        let config_seeds: &[&[u8]] = &[b"config", &[config.bump]];
+        let signer_seeds = &[config_seeds];
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
+            to: ctx.accounts.user_usdc.to_account_info(),
+            authority: config.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_interface::transfer_checked(cpi_ctx, refund, ctx.accounts.usdc_mint.decimals)?;
+    }
+    
+    if let Some(auto_renewal) = &ctx.accounts.auto_renewal {
+        if auto_renewal.token_account == ctx.accounts.user_usdc.key() {
+            let cpi_accounts = Revoke {
+                source: ctx.accounts.user_usdc.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+            token_interface::revoke(cpi_ctx)?;
+        }
+        emit!(AutoRenewDisabled {
+            user: ctx.accounts.user.key(),
+        });
+    }
+    
+    subscription.expires_at = now;
+    subscription.total_refunded += refund;
+    // What was not refunded is now revenue
+    release_all_periods(subscription, &mut ctx.accounts.vault_ledger);
+    subscription.auto_renew = false;
+    
+    emit!(SubscriptionCancelled {
+        user: ctx.accounts.user.key(),
+        tier: subscription.tier,
+        refund,
+        fee,
+        mint,
+        cancelled_at: now,
+    });
+    
+    Ok(())
+}
+
+/// Price (in `mint` base units) and duration for a subscription tier. An
+/// admin-set price for `mint` wins over the converted USD price.
+pub fn tier_terms(config: &ProgramConfig, tier_config: &TierConfig, mint: &Pubkey) -> Result<(u64, i64)> {
+    let price = match tier_config.mint_price(mint) {
+        Some(price) => {
+            require!(config.payment_mint(mint).is_some(), ErrorCode::UnsupportedPaymentMint);
+            price
+        }
+        None => mint_price(config, tier_config.usd_price, mint)?,
+    };
+    Ok((price, tier_config.duration))
+}
+
+/// Like `tier_terms`, but charges `subscription`'s grandfathered price while
+/// its lock holds. A later price cut still applies.
+pub fn locked_tier_terms(
+    config: &ProgramConfig,
+    subscription: &Subscription,
+    tier_config: &TierConfig,
+    mint: &Pubkey,
+    now: i64,
+) -> Result<(u64, i64)> {
+    let usd_price = locked_usd_price(config, subscription, tier_config, now);
+    let locked_mint_price =
+        subscription.locked_mint_price(tier_config.tier_id, mint, now, config.renewal_grace_period);
+    discounted_tier_terms(config, tier_config, mint, usd_price, locked_mint_price)
+}
+
+/// Like `tier_terms`, but at `usd_price` when that is below the list price,
+/// and never above `locked_mint_price`, a price locked in `mint` itself.
+pub fn discounted_tier_terms(
+    config: &ProgramConfig,
+    tier_config: &TierConfig,
+    mint: &Pubkey,
+    usd_price: u64,
+    locked_mint_price: Option<u64>,
+) -> Result<(u64, i64)> {
+    let (list_price, duration) = tier_terms(config, tier_config, mint)?;
+    let mut price = list_price;
+    if usd_price < tier_config.usd_price {
+        // The lock discounts every mint's price by the same ratio as the USD price
+        let scaled = (list_price as u128)
+            .checked_mul(usd_price as u128)
+            .and_then(|scaled| scaled.checked_div(tier_config.usd_price as u128))
+            .ok_or(ErrorCode::MathOverflow)?;
+        price = scaled as u64;
+    }
+    // In the mint the lock started in, a raised admin-set price does not apply either
+    if let Some(locked) = locked_mint_price {
+        price = price.min(locked);
+    }
+    Ok((price, duration))
+}
+
+/// USD price (6 decimals) `subscription` pays for a period of `tier_config`.
+pub fn locked_usd_price(
+    config: &ProgramConfig,
+    subscription: &Subscription,
+    tier_config: &TierConfig,
+    now: i64,
+) -> u64 {
+    match subscription.locked_price(tier_config.tier_id, now, config.renewal_grace_period) {
+        Some(locked) => locked.min(tier_config.usd_price),
+        None => tier_config.usd_price,
+    }
+}
+
+/// Starts a price lock at the tier's current list price when `subscription`
+/// is new or has lapsed past the grace period. Call before crediting it.
+pub fn start_price_lock(
+    config: &ProgramConfig,
+    subscription: &mut Subscription,
+    tier_config: &TierConfig,
+    mint: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    if subscription.expires_at > now
+        || subscription.locked_price(tier_config.tier_id, now, config.renewal_grace_period).is_some()
+    {
+        return Ok(());
+    }
+    reset_price_lock(config, subscription, tier_config, mint, now)
+}
+
+/// Locks the tier's current list price, in USD and in `mint`, for
+/// `config.price_lock_duration`.
+pub fn reset_price_lock(
+    config: &ProgramConfig,
+    subscription: &mut Subscription,
+    tier_config: &TierConfig,
+    mint: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    subscription.locked_usd_price = tier_config.usd_price;
+    // SOL is priced from the USD price through the oracle
+    (subscription.locked_mint, subscription.locked_mint_price) = if config.payment_mint(mint).is_some() {
+        (*mint, tier_terms(config, tier_config, mint)?.0)
+    } else {
+        (Pubkey::default(), 0)
+    };
+    subscription.price_lock_expires_at = if config.price_lock_duration > 0 {
+        now.saturating_add(config.price_lock_duration)
+    } else {
+        0
+    };
+    Ok(())
+}
+
+fn mint_price(config: &ProgramConfig, usd_price: u64, mint: &Pubkey) -> Result<u64> {
+    let payment_mint = match config.payment_mint(mint) {
+        Some(entry) => entry,
+        None => return err!(ErrorCode::UnsupportedPaymentMint),
+    };
+    
+    PaymentMint::scale_price(usd_price, payment_mint.decimals)
+}
@@ -0,0 +1,398 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::instructions::audit::log_admin_action;
+use crate::instructions::multisig::authorize_admin;
+use crate::instructions::subscription::tier_terms;
+
+#[derive(Accounts)]
+#[instruction(tier_id: u8)]
+pub struct CreateTier<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = TierConfig::LEN,
+        seeds = [b"tier", tier_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    /// Approved multisig proposal, required when `admin` is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(tier_id: u8)]
+pub struct UpdateTier<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"tier", tier_id.to_le_bytes().as_ref()],
+        bump = tier_config.bump
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    /// Approved multisig proposal, required when `admin` is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+}
+
+#[derive(Accounts)]
+#[instruction(tier_id: u8)]
+pub struct RetireTier<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"tier", tier_id.to_le_bytes().as_ref()],
+        bump = tier_config.bump,
+        constraint = tier_config.active @ ErrorCode::TierInactive
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    /// Approved multisig proposal, required when `admin` is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+}
+
+#[derive(Accounts)]
+#[instruction(tier_id: u8)]
+pub struct SetTierMintPrice<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"tier", tier_id.to_le_bytes().as_ref()],
+        bump = tier_config.bump
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    /// Approved multisig proposal, required when `admin` is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+}
+
+#[event]
+pub struct TierCreated {
+    pub admin: Pubkey,
+    pub tier_id: u8,
+    pub name: [u8; 32],
+    pub usd_price: u64,
+    pub duration: i64,
+    pub features: u32,
+}
+
+#[event]
+pub struct TierUpdated {
+    pub admin: Pubkey,
+    pub tier_id: u8,
+    pub old_usd_price: u64,
+    pub new_usd_price: u64,
+    pub old_duration: i64,
+    pub new_duration: i64,
+    pub old_features: u32,
+    pub new_features: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TierMintPriceUpdated {
+    pub admin: Pubkey,
+    pub tier_id: u8,
+    pub mint: Pubkey,
+    pub old_price: Option<u64>,
+    pub new_price: Option<u64>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TierRetired {
+    pub admin: Pubkey,
+    pub tier_id: u8,
+    pub timestamp: i64,
+}
+
+pub fn create_tier(
+    ctx: Context<CreateTier>,
+    tier_id: u8,
+    name: [u8; 32],
+    usd_price: u64,
+    duration: i64,
+    features: u32,
+) -> Result<()> {
+    if tier_id == 0 || tier_id > TierConfig::MAX_TIER_ID {
+        return err!(ErrorCode::InvalidTier);
+    }
+    require!(usd_price > 0, ErrorCode::InvalidPrice);
+    require!(duration > 0, ErrorCode::InvalidDuration);
+    
+    authorize_admin(
+        &ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &mut ctx.accounts.proposal,
+        AdminAction::CreateTier { tier_id, name, usd_price, duration, features },
+    )?;
+    
+    let tier_config = &mut ctx.accounts.tier_config;
+    tier_config.tier_id = tier_id;
+    tier_config.name = name;
+    tier_config.usd_price = usd_price;
+    tier_config.duration = duration;
+    tier_config.features = features;
+    tier_config.active = true;
+    tier_config.bump = ctx.bumps.tier_config;
+    
+    emit!(TierCreated {
+        admin: ctx.accounts.admin.key(),
+        tier_id,
+        name,
+        usd_price,
+        duration,
+        features,
+    });
+    
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::CreateTier,
+        ctx.accounts.admin.key(),
+        ctx.accounts.tier_config.key(),
+    )?;
+    
+    Ok(())
+}
+
+pub fn update_tier(
+    ctx: Context<UpdateTier>,
+    tier_id: u8,
+    usd_price: Option<u64>,
+    duration: Option<i64>,
+    features: Option<u32>,
+) -> Result<()> {
+    authorize_admin(
+        &ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &mut ctx.accounts.proposal,
+        AdminAction::UpdateTier { tier_id, usd_price, duration, features },
+    )?;
+    
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::UpdateTier,
+        ctx.accounts.admin.key(),
+        ctx.accounts.tier_config.key(),
+    )?;
+    
+    // Anything that leaves subscribers worse off goes through the timelocked
+    // queue: a higher price, a shorter period or a removed feature
+    let tier_config = &mut ctx.accounts.tier_config;
+    if usd_price.map_or(false, |price| price > tier_config.usd_price)
+        || duration.map_or(false, |duration| duration < tier_config.duration)
+        || features.map_or(false, |features| tier_config.features & !features != 0)
+    {
+        return err!(ErrorCode::PriceIncreaseRequiresTimelock);
+    }
+    
+    apply_tier_update(tier_config, ctx.accounts.admin.key(), usd_price, duration, features)
+}
+
+/// Validates and writes the given fields, emitting `TierUpdated`.
+/// Shared by `update_tier` and `execute_config_change`.
+pub fn apply_tier_update(
+    tier_config: &mut TierConfig,
+    admin: Pubkey,
+    usd_price: Option<u64>,
+    duration: Option<i64>,
+    features: Option<u32>,
+) -> Result<()> {
+    // Snapshot current values for the event
+    let old_usd_price = tier_config.usd_price;
+    let old_duration = tier_config.duration;
+    let old_features = tier_config.features;
+    
+    if let Some(price) = usd_price {
+        require!(price > 0, ErrorCode::InvalidPrice);
+        tier_config.usd_price = price;
+    }
+    if let Some(duration) = duration {
+        require!(duration > 0, ErrorCode::InvalidDuration);
+        tier_config.duration = duration;
+    }
+    if let Some(features) = features {
+        tier_config.features = features;
+    }
+    
+    emit!(TierUpdated {
+        admin,
+        tier_id: tier_config.tier_id,
+        old_usd_price,
+        new_usd_price: tier_config.usd_price,
+        old_duration,
+        new_duration: tier_config.duration,
+        old_features,
+        new_features: tier_config.features,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Sets the tier's price in `mint` base units, or with `None` drops it so the
+/// mint is priced from the tier's USD price again.
+pub fn set_tier_mint_price(
+    ctx: Context<SetTierMintPrice>,
+    tier_id: u8,
+    mint: Pubkey,
+    price: Option<u64>,
+) -> Result<()> {
+    authorize_admin(
+        &ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &mut ctx.accounts.proposal,
+        AdminAction::SetTierMintPrice { tier_id, mint, price },
+    )?;
+    
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::SetTierMintPrice,
+        ctx.accounts.admin.key(),
+        ctx.accounts.tier_config.key(),
+    )?;
+    
+    // Same rule as update_tier: raising what subscribers pay in this mint
+    // goes through the timelocked queue
+    let config = &ctx.accounts.config;
+    let tier_config = &mut ctx.accounts.tier_config;
+    if let Some(payment_mint) = config.payment_mint(&mint) {
+        let (current_price, _) = tier_terms(config, tier_config, &mint)?;
+        let new_price = match price {
+            Some(price) => price,
+            None => PaymentMint::scale_price(tier_config.usd_price, payment_mint.decimals)?,
+        };
+        if new_price > current_price {
+            return err!(ErrorCode::PriceIncreaseRequiresTimelock);
+        }
+    }
+    
+    apply_tier_mint_price(config, tier_config, ctx.accounts.admin.key(), mint, price)
+}
+
+/// Validates and writes a per-mint price, emitting `TierMintPriceUpdated`.
+/// Shared by `set_tier_mint_price` and `execute_config_change`.
+pub fn apply_tier_mint_price(
+    config: &ProgramConfig,
+    tier_config: &mut TierConfig,
+    admin: Pubkey,
+    mint: Pubkey,
+    price: Option<u64>,
+) -> Result<()> {
+    let old_price = tier_config.mint_price(&mint);
+    
+    // Drop the old entry along with any left behind by removed mints
+    tier_config
+        .mint_prices
+        .retain(|entry| entry.mint != mint && config.payment_mint(&entry.mint).is_some());
+    
+    if let Some(price) = price {
+        require!(price > 0, ErrorCode::InvalidPrice);
+        require!(config.payment_mint(&mint).is_some(), ErrorCode::UnsupportedPaymentMint);
+        tier_config.mint_prices.push(TierMintPrice { mint, price });
+    }
+    
+    emit!(TierMintPriceUpdated {
+        admin,
+        tier_id: tier_config.tier_id,
+        mint,
+        old_price,
+        new_price: price,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Retired tiers stay readable so existing subscribers keep access until expiry,
+/// but can no longer be bought, renewed or switched to.
+pub fn retire_tier(ctx: Context<RetireTier>, tier_id: u8) -> Result<()> {
+    authorize_admin(
+        &ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &mut ctx.accounts.proposal,
+        AdminAction::RetireTier { tier_id },
+    )?;
+    
+    ctx.accounts.tier_config.active = false;
+    
+    emit!(TierRetired {
+        admin: ctx.accounts.admin.key(),
+        tier_id,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::RetireTier,
+        ctx.accounts.admin.key(),
+        ctx.accounts.tier_config.key(),
+    )?;
+    
+    Ok(())
+}
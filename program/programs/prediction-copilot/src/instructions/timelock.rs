@@ -0,0 +1,325 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::instructions::audit::log_admin_action;
+use crate::instructions::config::apply_config_update;
+use crate::instructions::multisig::authorize_admin;
+use crate::instructions::tier::{apply_tier_mint_price, apply_tier_update};
+
+#[derive(Accounts)]
+pub struct QueueConfigChange<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = PendingConfigChange::LEN,
+        seeds = [b"pending_config"],
+        bump
+    )]
+    pub pending_change: Account<'info, PendingConfigChange>,
+    
+    /// New treasury, a token account for an accepted payment mint
+    #[account(
+        constraint = config.payment_mint(&treasury.mint).is_some() @ ErrorCode::UnsupportedPaymentMint
+    )]
+    pub treasury: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Approved multisig proposal, required when `admin` is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteConfigChange<'info> {
+    pub executor: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        close = queued_by,
+        seeds = [b"pending_config"],
+        bump = pending_change.bump
+    )]
+    pub pending_change: Account<'info, PendingConfigChange>,
+    
+    /// CHECK: Rent refund destination, must match pending_change.queued_by
+    #[account(mut, address = pending_change.queued_by @ ErrorCode::Unauthorized)]
+    pub queued_by: UncheckedAccount<'info>,
+    
+    /// Required when the pending change targets a tier
+    #[account(
+        mut,
+        seeds = [b"tier", &[tier_config.tier_id]],
+        bump = tier_config.bump
+    )]
+    pub tier_config: Option<Account<'info, TierConfig>>,
+    
+    /// Required when the pending change sets a treasury, rechecked in case
+    /// its mint stopped being accepted while queued
+    #[account(
+        constraint = config.payment_mint(&treasury.mint).is_some() @ ErrorCode::UnsupportedPaymentMint
+    )]
+    pub treasury: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+}
+
+#[derive(Accounts)]
+pub struct CancelConfigChange<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        close = queued_by,
+        seeds = [b"pending_config"],
+        bump = pending_change.bump
+    )]
+    pub pending_change: Account<'info, PendingConfigChange>,
+    
+    /// CHECK: Rent refund destination, must match pending_change.queued_by
+    #[account(mut, address = pending_change.queued_by @ ErrorCode::Unauthorized)]
+    pub queued_by: UncheckedAccount<'info>,
+    
+    /// Approved multisig proposal, required when `admin` is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+}
+
+#[event]
+pub struct ConfigChangeQueued {
+    pub queued_by: Pubkey,
+    pub tier_id: Option<u8>,
+    pub usd_price: Option<u64>,
+    pub duration: Option<i64>,
+    pub features: Option<u32>,
+    pub price_mint: Option<Pubkey>,
+    pub mint_price: Option<u64>,
+    pub treasury: Option<Pubkey>,
+    pub eta: i64,
+}
+
+#[event]
+pub struct ConfigChangeExecuted {
+    pub executor: Pubkey,
+    pub eta: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigChangeCancelled {
+    pub admin: Pubkey,
+    pub eta: i64,
+    pub timestamp: i64,
+}
+
+pub fn queue_config_change(
+    ctx: Context<QueueConfigChange>,
+    tier_id: Option<u8>,
+    usd_price: Option<u64>,
+    duration: Option<i64>,
+    features: Option<u32>,
+    price_mint: Option<Pubkey>,
+    mint_price: Option<u64>,
+    eta: i64,
+) -> Result<()> {
+    let treasury = ctx.accounts.treasury.as_ref().map(|treasury| treasury.key());
+    authorize_admin(
+        &ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &mut ctx.accounts.proposal,
+        AdminAction::QueueConfigChange {
+            tier_id,
+            usd_price,
+            duration,
+            features,
+            price_mint,
+            mint_price,
+            treasury,
+            eta,
+        },
+    )?;
+    
+    let now = Clock::get()?.unix_timestamp;
+    if eta < now + PendingConfigChange::MIN_DELAY {
+        return err!(ErrorCode::TimelockTooShort);
+    }
+    
+    // Reject bad values up front so the queue never shows an unexecutable change
+    match tier_id {
+        Some(id) if id == 0 || id > TierConfig::MAX_TIER_ID => return err!(ErrorCode::InvalidTier),
+        None if usd_price.is_some()
+            || duration.is_some()
+            || features.is_some()
+            || price_mint.is_some() =>
+        {
+            return err!(ErrorCode::InvalidTier)
+        }
+        _ => {}
+    }
+    match (price_mint, mint_price) {
+        (Some(mint), Some(price)) if price > 0 => {
+            require!(ctx.accounts.config.payment_mint(&mint).is_some(), ErrorCode::UnsupportedPaymentMint);
+        }
+        (Some(_), Some(_)) | (None, None) => {}
+        _ => return err!(ErrorCode::InvalidPrice),
+    }
+    if usd_price == Some(0) {
+        return err!(ErrorCode::InvalidPrice);
+    }
+    if duration.map_or(false, |d| d <= 0) {
+        return err!(ErrorCode::InvalidDuration);
+    }
+    
+    let pending = &mut ctx.accounts.pending_change;
+    pending.queued_by = ctx.accounts.admin.key();
+    pending.tier_id = tier_id;
+    pending.usd_price = usd_price;
+    pending.duration = duration;
+    pending.features = features;
+    pending.price_mint = price_mint;
+    pending.mint_price = mint_price;
+    pending.treasury = treasury;
+    pending.queued_at = now;
+    pending.eta = eta;
+    pending.bump = ctx.bumps.pending_change;
+    
+    emit!(ConfigChangeQueued {
+        queued_by: pending.queued_by,
+        tier_id,
+        usd_price,
+        duration,
+        features,
+        price_mint,
+        mint_price,
+        treasury,
+        eta,
+    });
+    
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::QueueConfigChange,
+        ctx.accounts.admin.key(),
+        ctx.accounts.pending_change.key(),
+    )?;
+    
+    Ok(())
+}
+
+pub fn execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+    let pending = &ctx.accounts.pending_change;
+    let now = Clock::get()?.unix_timestamp;
+    
+    if now < pending.eta {
+        return err!(ErrorCode::TimelockNotElapsed);
+    }
+    
+    if let Some(tier_id) = pending.tier_id {
+        let tier_config = match &mut ctx.accounts.tier_config {
+            Some(tier_config) if tier_config.tier_id == tier_id => tier_config,
+            _ => return err!(ErrorCode::InvalidTier),
+        };
+        apply_tier_update(
+            tier_config,
+            pending.queued_by,
+            pending.usd_price,
+            pending.duration,
+            pending.features,
+        )?;
+        if let Some(mint) = pending.price_mint {
+            // A queued 0 drops the override
+            let price = pending.mint_price.filter(|price| *price > 0);
+            apply_tier_mint_price(&ctx.accounts.config, tier_config, pending.queued_by, mint, price)?;
+        }
+    }
+    
+    if let Some(treasury) = pending.treasury {
+        if ctx.accounts.treasury.as_ref().map(|account| account.key()) != Some(treasury) {
+            return err!(ErrorCode::Unauthorized);
+        }
+    }
+    apply_config_update(&mut ctx.accounts.config, pending.queued_by, pending.treasury)?;
+    
+    emit!(ConfigChangeExecuted {
+        executor: ctx.accounts.executor.key(),
+        eta: pending.eta,
+        timestamp: now,
+    });
+    
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::ExecuteConfigChange,
+        ctx.accounts.executor.key(),
+        ctx.accounts.pending_change.key(),
+    )?;
+    
+    Ok(())
+}
+
+/// A multisig proposal names the change it cancels by its `queued_at` and
+/// `eta`, so it cannot cancel a change queued after it was approved.
+pub fn cancel_config_change(ctx: Context<CancelConfigChange>) -> Result<()> {
+    let pending = &ctx.accounts.pending_change;
+    authorize_admin(
+        &ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &mut ctx.accounts.proposal,
+        AdminAction::CancelConfigChange { queued_at: pending.queued_at, eta: pending.eta },
+    )?;
+    
+    emit!(ConfigChangeCancelled {
+        admin: ctx.accounts.admin.key(),
+        eta: ctx.accounts.pending_change.eta,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::CancelConfigChange,
+        ctx.accounts.admin.key(),
+        ctx.accounts.pending_change.key(),
+    )?;
+    
+    Ok(())
+}
@@ -0,0 +1,274 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::*;
+use crate::errors::ErrorCode;
+use crate::instructions::audit::log_admin_action;
+use crate::instructions::multisig::authorize_admin;
+use crate::instructions::subscription::prune_expired_periods;
+
+#[derive(Accounts)]
+pub struct InitializeVault<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        constraint = config.payment_mint(&mint.key()).is_some() @ ErrorCode::UnsupportedPaymentMint
+    )]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"vault", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = config,
+        token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = VaultLedger::LEN,
+        seeds = [b"vault_ledger", mint.key().as_ref()],
+        bump
+    )]
+    pub vault_ledger: Account<'info, VaultLedger>,
+    
+    /// SPL Token or Token-2022, whichever owns `mint`
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct SetRevenueSplit<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = RevenueSplit::LEN,
+        seeds = [b"revenue_split"],
+        bump
+    )]
+    pub revenue_split: Account<'info, RevenueSplit>,
+    
+    /// Approved multisig proposal, required when `admin` is not config.admin
+    #[account(mut)]
+    pub proposal: Option<Account<'info, MultisigProposal>>,
+    
+    #[account(
+        mut,
+        seeds = [b"admin_log"],
+        bump = admin_log.load()?.bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Remaining accounts: one token account per beneficiary, in split order,
+/// each for `mint` and owned by that beneficiary's wallet.
+#[derive(Accounts)]
+pub struct DistributeRevenue<'info> {
+    pub caller: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        seeds = [b"revenue_split"],
+        bump = revenue_split.bump
+    )]
+    pub revenue_split: Account<'info, RevenueSplit>,
+    
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = config,
+        token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [b"vault_ledger", mint.key().as_ref()],
+        bump = vault_ledger.bump
+    )]
+    pub vault_ledger: Account<'info, VaultLedger>,
+    
+    /// SPL Token or Token-2022, whichever owns `mint`
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseExpiredPeriods<'info> {
+    #[account(
+        mut,
+        seeds = [b"subscription", subscription.user.as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault_ledger", vault_ledger.mint.as_ref()],
+        bump = vault_ledger.bump
+    )]
+    pub vault_ledger: Account<'info, VaultLedger>,
+}
+
+#[event]
+pub struct RevenueSplitUpdated {
+    pub admin: Pubkey,
+    pub beneficiaries: Vec<Beneficiary>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RevenueDistributed {
+    pub mint: Pubkey,
+    pub total: u64,
+    pub beneficiary_count: u8,
+    pub caller: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+    let vault_ledger = &mut ctx.accounts.vault_ledger;
+    vault_ledger.mint = ctx.accounts.mint.key();
+    vault_ledger.refundable = 0;
+    vault_ledger.bump = ctx.bumps.vault_ledger;
+    
+    Ok(())
+}
+
+pub fn set_revenue_split(
+    ctx: Context<SetRevenueSplit>,
+    beneficiaries: Vec<Beneficiary>,
+) -> Result<()> {
+    authorize_admin(
+        &ctx.accounts.config,
+        ctx.accounts.admin.key(),
+        &mut ctx.accounts.proposal,
+        AdminAction::SetRevenueSplit { beneficiaries: beneficiaries.clone() },
+    )?;
+    
+    if beneficiaries.is_empty() || beneficiaries.len() > RevenueSplit::MAX_BENEFICIARIES {
+        return err!(ErrorCode::InvalidRevenueSplit);
+    }
+    let total_bps = beneficiaries
+        .iter()
+        .try_fold(0u16, |sum, b| sum.checked_add(b.share_bps));
+    if total_bps != Some(RevenueSplit::TOTAL_BPS) {
+        return err!(ErrorCode::InvalidRevenueSplit);
+    }
+    
+    let revenue_split = &mut ctx.accounts.revenue_split;
+    let now = Clock::get()?.unix_timestamp;
+    revenue_split.beneficiaries = beneficiaries;
+    revenue_split.updated_at = now;
+    revenue_split.bump = ctx.bumps.revenue_split;
+    
+    emit!(RevenueSplitUpdated {
+        admin: ctx.accounts.admin.key(),
+        beneficiaries: revenue_split.beneficiaries.clone(),
+        timestamp: now,
+    });
+    
+    log_admin_action(
+        &ctx.accounts.admin_log,
+        AdminLogAction::SetRevenueSplit,
+        ctx.accounts.admin.key(),
+        ctx.accounts.revenue_split.key(),
+    )?;
+    
+    Ok(())
+}
+
+pub fn distribute_revenue<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, DistributeRevenue<'info>>,
+) -> Result<()> {
+    let beneficiaries = &ctx.accounts.revenue_split.beneficiaries;
+    if ctx.remaining_accounts.len() != beneficiaries.len() {
+        return err!(ErrorCode::InvalidBeneficiaryAccount);
+    }
+    
+    if ctx.accounts.vault.amount == 0 {
+        return err!(ErrorCode::NothingToDistribute);
+    }
+    // Payments that can still be refunded stay in the vault
+    let total = ctx.accounts.vault.amount.saturating_sub(ctx.accounts.vault_ledger.refundable);
+    if total == 0 {
+        return err!(ErrorCode::NothingDistributable);
+    }
+    
+    let config_seeds: &[&[u8]] = &[b"config", &[ctx.accounts.config.bump]];
+    let signer_seeds = &[config_seeds];
+    
+    // Rounding dust stays in the vault for the next distribution
+    for (beneficiary, account_info) in beneficiaries.iter().zip(ctx.remaining_accounts.iter()) {
+        let destination: InterfaceAccount<TokenAccount> = InterfaceAccount::try_from(account_info)?;
+        if destination.mint != ctx.accounts.mint.key() || destination.owner != beneficiary.wallet {
+            return err!(ErrorCode::InvalidBeneficiaryAccount);
+        }
+        
+        let share = (total as u128 * beneficiary.share_bps as u128
+            / RevenueSplit::TOTAL_BPS as u128) as u64;
+        if share == 0 {
+            continue;
+        }
+        
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: account_info.clone(),
+            authority: ctx.accounts.config.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_interface::transfer_checked(cpi_ctx, share, ctx.accounts.mint.decimals)?;
+    }
+    
+    emit!(RevenueDistributed {
+        mint: ctx.accounts.mint.key(),
+        total,
+        beneficiary_count: beneficiaries.len() as u8,
+        caller: ctx.accounts.caller.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Permissionless crank that releases a subscription's ended periods from
+/// the vault ledger, so their cash becomes distributable.
+pub fn release_expired_periods(ctx: Context<ReleaseExpiredPeriods>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    prune_expired_periods(&mut ctx.accounts.subscription, &mut ctx.accounts.vault_ledger, now);
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct StartTrial<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = Subscription::LEN,
+        seeds = [b"subscription", user.key().as_ref()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    
+    /// Exists once a wallet has used its trial, so a second init fails
+    #[account(
+        init,
+        payer = user,
+        space = TrialClaim::LEN,
+        seeds = [b"trial", user.key().as_ref()],
+        bump
+    )]
+    pub trial_claim: Account<'info, TrialClaim>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.is_paused(ProgramConfig::PAUSE_SUBSCRIPTIONS) @ ErrorCode::ProgramPaused,
+        constraint = config.trials_enabled @ ErrorCode::TrialsDisabled
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        seeds = [b"tier", &[config.trial_tier]],
+        bump = tier_config.bump,
+        constraint = tier_config.active @ ErrorCode::TierInactive
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct TrialStarted {
+    pub user: Pubkey,
+    pub tier: u8,
+    pub expires_at: i64,
+}
+
+pub fn start_trial(ctx: Context<StartTrial>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let subscription = &mut ctx.accounts.subscription;
+    let user = ctx.accounts.user.key();
+    let now = Clock::get()?.unix_timestamp;
+    
+    // Trials are for new or lapsed users only
+    if subscription.expires_at > now {
+        return err!(ErrorCode::TrialUnavailable);
+    }
+    
+    subscription.user = user;
+    subscription.tier = config.trial_tier;
+    subscription.started_at = now;
+    subscription.expires_at = now + config.trial_duration;
+    subscription.is_trial = true;
+    subscription.bump = ctx.bumps.subscription;
+    
+    let trial_claim = &mut ctx.accounts.trial_claim;
+    trial_claim.user = user;
+    trial_claim.claimed_at = now;
+    trial_claim.bump = ctx.bumps.trial_claim;
+    
+    emit!(TrialStarted {
+        user,
+        tier: subscription.tier,
+        expires_at: subscription.expires_at,
+    });
+    
+    Ok(())
+}
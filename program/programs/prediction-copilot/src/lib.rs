@@ -5,6 +5,7 @@ pub mod errors;
 pub mod instructions;
 
 use instructions::*;
+use state::*;
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
@@ -12,26 +13,310 @@ declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 pub mod prediction_copilot {
     use super::*;
 
-    pub fn initialize_config(
-        ctx: Context<InitializeConfig>,
-        basic_price: u64,
-        pro_price: u64,
-        basic_duration: i64,
-        pro_duration: i64,
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+        instructions::config::initialize_config(ctx)
+    }
+
+    pub fn initialize_admin_log(ctx: Context<InitializeAdminLog>) -> Result<()> {
+        instructions::audit::initialize_admin_log(ctx)
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>) -> Result<()> {
+        instructions::config::update_config(ctx)
+    }
+
+    pub fn create_tier(
+        ctx: Context<CreateTier>,
+        tier_id: u8,
+        name: [u8; 32],
+        usd_price: u64,
+        duration: i64,
+        features: u32,
+    ) -> Result<()> {
+        instructions::tier::create_tier(ctx, tier_id, name, usd_price, duration, features)
+    }
+
+    pub fn update_tier(
+        ctx: Context<UpdateTier>,
+        tier_id: u8,
+        usd_price: Option<u64>,
+        duration: Option<i64>,
+        features: Option<u32>,
+    ) -> Result<()> {
+        instructions::tier::update_tier(ctx, tier_id, usd_price, duration, features)
+    }
+
+    pub fn set_tier_mint_price(
+        ctx: Context<SetTierMintPrice>,
+        tier_id: u8,
+        mint: Pubkey,
+        price: Option<u64>,
+    ) -> Result<()> {
+        instructions::tier::set_tier_mint_price(ctx, tier_id, mint, price)
+    }
+
+    pub fn retire_tier(ctx: Context<RetireTier>, tier_id: u8) -> Result<()> {
+        instructions::tier::retire_tier(ctx, tier_id)
+    }
+
+    pub fn queue_config_change(
+        ctx: Context<QueueConfigChange>,
+        tier_id: Option<u8>,
+        usd_price: Option<u64>,
+        duration: Option<i64>,
+        features: Option<u32>,
+        price_mint: Option<Pubkey>,
+        mint_price: Option<u64>,
+        eta: i64,
+    ) -> Result<()> {
+        instructions::timelock::queue_config_change(
+            ctx,
+            tier_id,
+            usd_price,
+            duration,
+            features,
+            price_mint,
+            mint_price,
+            eta,
+        )
+    }
+
+    pub fn execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+        instructions::timelock::execute_config_change(ctx)
+    }
+
+    pub fn cancel_config_change(ctx: Context<CancelConfigChange>) -> Result<()> {
+        instructions::timelock::cancel_config_change(ctx)
+    }
+
+    pub fn add_payment_mint(ctx: Context<AddPaymentMint>) -> Result<()> {
+        instructions::payment::add_payment_mint(ctx)
+    }
+
+    pub fn remove_payment_mint(ctx: Context<RemovePaymentMint>, mint: Pubkey) -> Result<()> {
+        instructions::payment::remove_payment_mint(ctx, mint)
+    }
+
+    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+        instructions::treasury::initialize_vault(ctx)
+    }
+
+    pub fn release_expired_periods(ctx: Context<ReleaseExpiredPeriods>) -> Result<()> {
+        instructions::treasury::release_expired_periods(ctx)
+    }
+
+    pub fn set_revenue_split(
+        ctx: Context<SetRevenueSplit>,
+        beneficiaries: Vec<Beneficiary>,
+    ) -> Result<()> {
+        instructions::treasury::set_revenue_split(ctx, beneficiaries)
+    }
+
+    pub fn distribute_revenue<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, DistributeRevenue<'info>>,
+    ) -> Result<()> {
+        instructions::treasury::distribute_revenue(ctx)
+    }
+
+    pub fn set_pause_flags(ctx: Context<SetPauseFlags>, pause_flags: u8) -> Result<()> {
+        instructions::config::set_pause_flags(ctx, pause_flags)
+    }
+
+    pub fn set_refund_policy(
+        ctx: Context<SetRefundPolicy>,
+        cancellation_fee_bps: u16,
+        refund_window: i64,
+    ) -> Result<()> {
+        instructions::config::set_refund_policy(ctx, cancellation_fee_bps, refund_window)
+    }
+
+    pub fn set_trial_config(
+        ctx: Context<SetTrialConfig>,
+        trials_enabled: bool,
+        trial_tier: u8,
+        trial_duration: i64,
     ) -> Result<()> {
-        instructions::config::initialize_config(
+        instructions::config::set_trial_config(ctx, trials_enabled, trial_tier, trial_duration)
+    }
+
+    pub fn create_promo_code(
+        ctx: Context<CreatePromoCode>,
+        code_hash: [u8; 32],
+        discount_kind: u8,
+        discount_value: u64,
+        allowed_tiers: u64,
+        max_redemptions: u32,
+        per_wallet_limit: u16,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::promo::create_promo_code(
             ctx,
-            basic_price,
-            pro_price,
-            basic_duration,
-            pro_duration,
+            code_hash,
+            discount_kind,
+            discount_value,
+            allowed_tiers,
+            max_redemptions,
+            per_wallet_limit,
+            expires_at,
         )
     }
 
+    pub fn deactivate_promo_code(
+        ctx: Context<DeactivatePromoCode>,
+        code_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::promo::deactivate_promo_code(ctx, code_hash)
+    }
+
+    pub fn set_price_lock_policy(
+        ctx: Context<SetPriceLockPolicy>,
+        price_lock_duration: i64,
+        renewal_grace_period: i64,
+    ) -> Result<()> {
+        instructions::config::set_price_lock_policy(ctx, price_lock_duration, renewal_grace_period)
+    }
+
+    pub fn set_referral_commission(
+        ctx: Context<SetReferralCommission>,
+        referral_commission_bps: u16,
+    ) -> Result<()> {
+        instructions::config::set_referral_commission(ctx, referral_commission_bps)
+    }
+
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        instructions::admin::propose_admin(ctx, new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::admin::accept_admin(ctx)
+    }
+
+    pub fn cancel_admin_transfer(ctx: Context<CancelAdminTransfer>) -> Result<()> {
+        instructions::admin::cancel_admin_transfer(ctx)
+    }
+
+    pub fn grant_role(ctx: Context<GrantRole>, role: u8, holder: Pubkey) -> Result<()> {
+        instructions::roles::grant_role(ctx, role, holder)
+    }
+
+    pub fn revoke_role(ctx: Context<RevokeRole>, role: u8, holder: Pubkey) -> Result<()> {
+        instructions::roles::revoke_role(ctx, role, holder)
+    }
+
+    pub fn create_multisig(
+        ctx: Context<CreateMultisig>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::multisig::create_multisig(ctx, signers, threshold)
+    }
+
+    pub fn create_proposal(ctx: Context<CreateProposal>, action: AdminAction) -> Result<()> {
+        instructions::multisig::create_proposal(ctx, action)
+    }
+
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        instructions::multisig::approve_proposal(ctx)
+    }
+
     pub fn subscribe(ctx: Context<Subscribe>, tier: u8) -> Result<()> {
         instructions::subscription::subscribe(ctx, tier)
     }
 
+    pub fn subscribe_with_sol(
+        ctx: Context<SubscribeWithSol>,
+        tier: u8,
+        max_lamports: u64,
+    ) -> Result<()> {
+        instructions::sol_payment::subscribe_with_sol(ctx, tier, max_lamports)
+    }
+
+    pub fn subscribe_as_pass(ctx: Context<SubscribeAsPass>, tier: u8, pass_id: u64) -> Result<()> {
+        instructions::pass::subscribe_as_pass(ctx, tier, pass_id)
+    }
+
+    pub fn redeem_pass(ctx: Context<RedeemPass>) -> Result<()> {
+        instructions::pass::redeem_pass(ctx)
+    }
+
+    pub fn burn_expired_pass(ctx: Context<BurnExpiredPass>) -> Result<()> {
+        instructions::pass::burn_expired_pass(ctx)
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        instructions::referral::register_referrer(ctx)
+    }
+
+    pub fn gift_subscription(
+        ctx: Context<GiftSubscription>,
+        tier: u8,
+        recipient: Pubkey,
+        gift_id: u64,
+        memo_hash: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::gift::gift_subscription(ctx, tier, recipient, gift_id, memo_hash)
+    }
+
+    pub fn purchase_org_subscription(
+        ctx: Context<PurchaseOrgSubscription>,
+        tier: u8,
+        seats: u16,
+    ) -> Result<()> {
+        instructions::org::purchase_org_subscription(ctx, tier, seats)
+    }
+
+    pub fn add_org_seats(ctx: Context<AddOrgSeats>, additional: u16) -> Result<()> {
+        instructions::org::add_org_seats(ctx, additional)
+    }
+
+    pub fn add_org_member(ctx: Context<AddOrgMember>, member: Pubkey) -> Result<()> {
+        instructions::org::add_org_member(ctx, member)
+    }
+
+    pub fn remove_org_member(ctx: Context<RemoveOrgMember>) -> Result<()> {
+        instructions::org::remove_org_member(ctx)
+    }
+
+    pub fn start_trial(ctx: Context<StartTrial>) -> Result<()> {
+        instructions::trial::start_trial(ctx)
+    }
+
+    pub fn enable_auto_renew(ctx: Context<EnableAutoRenew>, max_amount: u64) -> Result<()> {
+        instructions::renewal::enable_auto_renew(ctx, max_amount)
+    }
+
+    pub fn disable_auto_renew(ctx: Context<DisableAutoRenew>) -> Result<()> {
+        instructions::renewal::disable_auto_renew(ctx)
+    }
+
+    pub fn renew_subscription(ctx: Context<RenewSubscription>) -> Result<()> {
+        instructions::renewal::renew_subscription(ctx)
+    }
+
+    pub fn verify_subscription(ctx: Context<VerifySubscription>) -> Result<SubscriptionStatus> {
+        instructions::access::verify_subscription(ctx)
+    }
+
+    pub fn migrate_subscription(ctx: Context<MigrateSubscription>) -> Result<()> {
+        instructions::migrate::migrate_subscription(ctx)
+    }
+
+    pub fn migrate_config(
+        ctx: Context<MigrateConfig>,
+        basic_features: u32,
+        pro_features: u32,
+    ) -> Result<()> {
+        instructions::migrate::migrate_config(ctx, basic_features, pro_features)
+    }
+
+    pub fn change_tier(ctx: Context<ChangeTier>, new_tier: u8) -> Result<()> {
+        instructions::subscription::change_tier(ctx, new_tier)
+    }
+
+    pub fn cancel_subscription(ctx: Context<CancelSubscription>) -> Result<()> {
+        instructions::subscription::cancel_subscription(ctx)
+    }
+
     pub fn initialize_registry(ctx: Context<InitializeRegistry>) -> Result<()> {
         instructions::registry::initialize_registry(ctx)
     }
@@ -3,38 +3,635 @@ use anchor_lang::prelude::*;
 #[account]
 pub struct ProgramConfig {
     pub admin: Pubkey,              // Can update config
-    pub treasury: Pubkey,           // Receives subscription payments
-    pub basic_price: u64,           // Price in USDC (6 decimals) e.g., 5_000_000 = $5
-    pub pro_price: u64,             // e.g., 20_000_000 = $20
-    pub basic_duration: i64,        // Seconds, e.g., 2592000 = 30 days
-    pub pro_duration: i64,          
-    pub paused: bool,               // Emergency pause
+    pub treasury: Pubkey,           // Team treasury token account for the initial payment mint
+    pub pause_flags: u8,            // Bitmask of paused subsystems (see PAUSE_*)
     pub bump: u8,
+    pub pending_admin: Option<Pubkey>, // Proposed admin, must call accept_admin
+    pub payment_mints: Vec<PaymentMint>, // Accepted payment mints, max MAX_PAYMENT_MINTS
+    pub cancellation_fee_bps: u16,  // Fee withheld from prorated refunds
+    pub refund_window: i64,         // Seconds after a payment with a full, fee-free refund
+    pub trials_enabled: bool,       // Whether start_trial is open
+    pub trial_tier: u8,             // Tier granted by a trial
+    pub trial_duration: i64,        // Seconds, e.g., 604800 = 7 days
+    pub referral_commission_bps: u16, // Share of referred payments sent to the referrer
+    pub price_lock_duration: i64,   // Seconds a new subscription keeps its price, 0 = no lock
+    pub renewal_grace_period: i64,  // Seconds after expiry a lapsed subscription keeps its lock
 }
 
 impl ProgramConfig {
-    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 1; // 106
+    pub const MAX_PAYMENT_MINTS: usize = 4;
+    pub const MAX_CANCELLATION_FEE_BPS: u16 = 10_000;
+    pub const MAX_REFERRAL_COMMISSION_BPS: u16 = 5_000;
+    pub const SOL_USD_FEED_ID: &'static str =
+        "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d";
+    pub const MAX_SOL_PRICE_AGE: u64 = 60;          // Seconds
+    pub const MAX_SOL_PRICE_CONF_BPS: u64 = 100;    // Confidence interval / price
+    pub const MAX_PRICE_LOCK_DURATION: i64 = 10 * 365 * 24 * 60 * 60; // 10 years
+    pub const MAX_RENEWAL_GRACE_PERIOD: i64 = 365 * 24 * 60 * 60;     // 1 year
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 1 + (1 + 32)
+        + (4 + PaymentMint::LEN * Self::MAX_PAYMENT_MINTS) + 2 + 8 + 1 + 1 + 8 + 2 + 8 + 8; // 281
+
+    pub const PAUSE_SUBSCRIPTIONS: u8 = 1 << 0;
+    pub const PAUSE_SIGNALS: u8 = 1 << 1;
+    pub const PAUSE_REGISTRY: u8 = 1 << 2;
+    pub const PAUSE_MOVEMENTS: u8 = 1 << 3;
+    pub const PAUSE_ALL: u8 = Self::PAUSE_SUBSCRIPTIONS
+        | Self::PAUSE_SIGNALS
+        | Self::PAUSE_REGISTRY
+        | Self::PAUSE_MOVEMENTS;
+
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.pause_flags & flag != 0
+    }
+
+    pub fn payment_mint(&self, mint: &Pubkey) -> Option<&PaymentMint> {
+        self.payment_mints.iter().find(|entry| entry.mint == *mint)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct PaymentMint {
+    pub mint: Pubkey,               // SPL mint, e.g. USDC, USDT, PYUSD
+    pub decimals: u8,               // Mint decimals
+}
+
+impl PaymentMint {
+    pub const LEN: usize = 32 + 1; // 33
+    pub const USD_DECIMALS: u8 = 6;
+
+    /// Converts a USD list price (6 decimals) into base units of a mint.
+    pub fn scale_price(usd_price: u64, decimals: u8) -> Result<u64> {
+        let scaled = if decimals >= Self::USD_DECIMALS {
+            10u64
+                .checked_pow((decimals - Self::USD_DECIMALS) as u32)
+                .and_then(|factor| usd_price.checked_mul(factor))
+        } else {
+            10u64
+                .checked_pow((Self::USD_DECIMALS - decimals) as u32)
+                .map(|factor| usd_price / factor)
+        };
+        match scaled {
+            Some(price) if price > 0 => Ok(price),
+            _ => err!(crate::errors::ErrorCode::InvalidPrice),
+        }
+    }
+}
+
+#[account(zero_copy)]
+pub struct AdminLog {
+    pub total_entries: u64,         // Entries ever written, head = total % CAPACITY
+    pub bump: u8,
+    pub _padding: [u8; 7],
+    pub entries: [AdminLogEntry; 64], // Ring buffer, oldest overwritten first
+}
+
+impl AdminLog {
+    pub const CAPACITY: usize = 64;
+    pub const LEN: usize = 8 + 8 + 1 + 7 + AdminLogEntry::LEN * Self::CAPACITY; // 5656
+
+    pub fn append(&mut self, entry: AdminLogEntry) {
+        let index = (self.total_entries % Self::CAPACITY as u64) as usize;
+        self.entries[index] = entry;
+        self.total_entries += 1;
+    }
+}
+
+#[zero_copy]
+pub struct AdminLogEntry {
+    pub actor: Pubkey,              // Signer that performed the action
+    pub target: Pubkey,             // Primary account or key affected
+    pub slot: u64,
+    pub timestamp: i64,             // Unix timestamp
+    pub action: u8,                 // AdminLogAction
+    pub _padding: [u8; 7],
+}
+
+impl AdminLogEntry {
+    pub const LEN: usize = 32 + 32 + 8 + 8 + 1 + 7; // 88
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AdminLogAction {
+    UpdateConfig = 0,
+    SetPauseFlags = 1,
+    ProposeAdmin = 2,
+    AcceptAdmin = 3,
+    CancelAdminTransfer = 4,
+    GrantRole = 5,
+    RevokeRole = 6,
+    CreateMultisig = 7,
+    QueueConfigChange = 8,
+    ExecuteConfigChange = 9,
+    CancelConfigChange = 10,
+    AddPaymentMint = 11,
+    RemovePaymentMint = 12,
+    SetRevenueSplit = 13,
+    InitializeRegistry = 14,
+    AddTrader = 15,
+    UpdateTrader = 16,
+    RemoveTrader = 17,
+    SetRefundPolicy = 20,
+    SetTrialConfig = 21,
+    CreatePromoCode = 22,
+    DeactivatePromoCode = 23,
+    SetReferralCommission = 24,
+    CreateTier = 25,
+    UpdateTier = 26,
+    RetireTier = 27,
+    SetPriceLockPolicy = 28,
+    SetTierMintPrice = 29,
+}
+
+#[account]
+pub struct RevenueSplit {
+    pub beneficiaries: Vec<Beneficiary>, // Shares must sum to 10_000 bps
+    pub updated_at: i64,            // Unix timestamp
+    pub bump: u8,
+}
+
+impl RevenueSplit {
+    pub const MAX_BENEFICIARIES: usize = 8;
+    pub const TOTAL_BPS: u16 = 10_000;
+    pub const LEN: usize = 8 + (4 + Beneficiary::LEN * Self::MAX_BENEFICIARIES) + 8 + 1; // 293
+}
+
+#[account]
+pub struct VaultLedger {
+    pub mint: Pubkey,               // Mint of the vault this ledger tracks
+    pub refundable: u64,            // PaidPeriod amounts in `mint` not yet released
+    pub bump: u8,
+}
+
+impl VaultLedger {
+    pub const LEN: usize = 8 + 32 + 8 + 1; // 49
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct Beneficiary {
+    pub wallet: Pubkey,             // Owner of the receiving token accounts
+    pub share_bps: u16,             // Basis points of each distribution
+}
+
+impl Beneficiary {
+    pub const LEN: usize = 32 + 2; // 34
+}
+
+#[account]
+pub struct PendingConfigChange {
+    pub queued_by: Pubkey,          // Admin that queued the change, refunded on close
+    pub tier_id: Option<u8>,        // Tier whose terms change, None = no tier change
+    pub usd_price: Option<u64>,     // None = leave unchanged
+    pub duration: Option<i64>,
+    pub treasury: Option<Pubkey>,
+    pub queued_at: i64,             // Unix timestamp
+    pub eta: i64,                   // Earliest execution time
+    pub bump: u8,
+    pub features: Option<u32>,      // None = leave unchanged
+    pub price_mint: Option<Pubkey>, // Payment mint whose tier price changes
+    pub mint_price: Option<u64>,    // New price for price_mint, 0 = back to the USD price
+}
+
+impl PendingConfigChange {
+    pub const LEN: usize = 8 + 32 + (1 + 1) + (1 + 8) * 2 + (1 + 32) + 8 + 8 + 1 + (1 + 4)
+        + (1 + 32) + (1 + 8); // 157
+    pub const MIN_DELAY: i64 = 7 * 24 * 60 * 60; // 7 days notice
+}
+
+#[account]
+pub struct Multisig {
+    pub signers: Vec<Pubkey>,       // Up to MAX_SIGNERS keys
+    pub threshold: u8,              // Approvals needed to execute (M of N)
+    pub proposal_count: u64,        // Next proposal index
+    pub bump: u8,
+}
+
+impl Multisig {
+    pub const MAX_SIGNERS: usize = 10;
+    pub const LEN: usize = 8 + (4 + 32 * Self::MAX_SIGNERS) + 1 + 8 + 1; // 342
+}
+
+#[account]
+pub struct MultisigProposal {
+    pub multisig: Pubkey,           // Owning multisig
+    pub index: u64,                 // Proposal index within the multisig
+    pub proposer: Pubkey,           // Signer that created the proposal
+    pub action: AdminAction,        // Admin action to authorize
+    pub approvals: u16,             // Bitmask over multisig.signers
+    pub approved: bool,             // Threshold reached
+    pub executed: bool,             // Consumed by an admin instruction
+    pub created_at: i64,            // Unix timestamp
+    pub bump: u8,
+}
+
+impl MultisigProposal {
+    pub const LEN: usize = 8 + 32 + 8 + 32 + AdminAction::MAX_LEN + 2 + 1 + 1 + 8 + 1; // 419
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum AdminAction {
+    UpdateConfig { treasury: Option<Pubkey> },
+    SetPauseFlags { pause_flags: u8 },
+    ProposeAdmin { new_admin: Pubkey },
+    CancelAdminTransfer,
+    GrantRole { role: u8, holder: Pubkey },
+    RevokeRole { role: u8, holder: Pubkey },
+    RemoveTrader { polygon_address: [u8; 20] },
+    QueueConfigChange {
+        tier_id: Option<u8>,
+        usd_price: Option<u64>,
+        duration: Option<i64>,
+        features: Option<u32>,
+        price_mint: Option<Pubkey>,
+        mint_price: Option<u64>,
+        treasury: Option<Pubkey>,
+        eta: i64,
+    },
+    CancelConfigChange { queued_at: i64, eta: i64 },
+    AddPaymentMint { mint: Pubkey },
+    RemovePaymentMint { mint: Pubkey },
+    SetRevenueSplit { beneficiaries: Vec<Beneficiary> },
+    SetRefundPolicy { cancellation_fee_bps: u16, refund_window: i64 },
+    SetTrialConfig { trials_enabled: bool, trial_tier: u8, trial_duration: i64 },
+    CreatePromoCode {
+        code_hash: [u8; 32],
+        discount_kind: u8,
+        discount_value: u64,
+        allowed_tiers: u64,
+        max_redemptions: u32,
+        per_wallet_limit: u16,
+        expires_at: i64,
+    },
+    DeactivatePromoCode { code_hash: [u8; 32] },
+    SetReferralCommission { referral_commission_bps: u16 },
+    CreateTier {
+        tier_id: u8,
+        name: [u8; 32],
+        usd_price: u64,
+        duration: i64,
+        features: u32,
+    },
+    UpdateTier {
+        tier_id: u8,
+        usd_price: Option<u64>,
+        duration: Option<i64>,
+        features: Option<u32>,
+    },
+    RetireTier { tier_id: u8 },
+    SetPriceLockPolicy { price_lock_duration: i64, renewal_grace_period: i64 },
+    SetTierMintPrice { tier_id: u8, mint: Pubkey, price: Option<u64> },
+    InitializeAdminLog,
+    InitializeRegistry,
+    CreateMultisig { signers: Vec<Pubkey>, threshold: u8 },
+    AddTrader {
+        polygon_address: [u8; 20],
+        solana_address: Option<Pubkey>,
+        tier: u8,
+        total_pnl: i64,
+        win_rate: u16,
+        trade_count: u32,
+        total_volume: u64,
+        roi: i32,
+    },
+    UpdateTrader {
+        polygon_address: [u8; 20],
+        tier: u8,
+        total_pnl: i64,
+        win_rate: u16,
+        trade_count: u32,
+        total_volume: u64,
+        roi: i32,
+        last_trade_at: i64,
+    },
+}
+
+impl AdminAction {
+    pub const MAX_LEN: usize = 1 + 4 + 32 * Multisig::MAX_SIGNERS + 1; // 326, CreateMultisig
+}
+
+#[account]
+pub struct RoleGrant {
+    pub holder: Pubkey,             // Key that holds the role
+    pub role: u8,                   // 1 = publisher, 2 = keeper, 3 = curator
+    pub granted_by: Pubkey,         // Admin that granted it
+    pub granted_at: i64,            // Unix timestamp
+    pub bump: u8,
+}
+
+impl RoleGrant {
+    pub const LEN: usize = 8 + 32 + 1 + 32 + 8 + 1; // 82
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Admin = 0,      // Held by config.admin only, rotated via propose/accept_admin
+    Publisher = 1,  // Publishes Pyth signals
+    Keeper = 2,     // Records whale movements
+    Curator = 3,    // Adds, updates and removes tracked traders
 }
 
 #[account]
 pub struct Subscription {
     pub user: Pubkey,               // Subscriber's wallet
-    pub tier: u8,                   // 0 = none, 1 = basic, 2 = pro
+    pub tier: u8,                   // TierConfig id, 0 = none
     pub started_at: i64,            // Unix timestamp
     pub expires_at: i64,            // Unix timestamp
-    pub total_paid: u64,            // Cumulative USDC paid
+    pub total_paid: u64,            // Cumulative token payments (payment mint base units)
     pub bump: u8,
+    pub total_refunded: u64,        // Cumulative refunds from cancellations
+    pub last_payment_at: i64,       // Unix timestamp of the latest payment
+    pub is_trial: bool,             // Current period is an unpaid trial
+    pub referrer: Option<Pubkey>,   // Bound on the first payment, never changes
+    pub locked_usd_price: u64,      // Grandfathered USD price per period (6 decimals)
+    pub price_lock_expires_at: i64, // Unix timestamp, locked price applies until then
+    pub auto_renew: bool,           // An AutoRenewal account exists for this user
+    pub total_paid_lamports: u64,   // Cumulative SOL payments, never refundable
+    pub locked_mint: Pubkey,        // Mint the lock started in, default for SOL
+    pub locked_mint_price: u64,     // Grandfathered price per period in locked_mint
+    pub paid_periods: Vec<PaidPeriod>, // Refundable token payments, oldest first, max MAX_PAID_PERIODS
 }
 
 impl Subscription {
-    pub const LEN: usize = 8 + 32 + 1 + 8 + 8 + 8 + 1; // 66
+    pub const MAX_PAID_PERIODS: usize = 12;
+    pub const LEN: usize = 8 + 32 + 1 + 8 + 8 + 8 + 1 + 8 + 8 + 1 + (1 + 32) + 8 + 8 + 1 + 8 + 32 + 8
+        + (4 + PaidPeriod::LEN * Self::MAX_PAID_PERIODS); // 953
+
+    /// Tier the holder can access at `now`, if any
+    pub fn active_tier(&self, now: i64) -> Option<u8> {
+        (self.expires_at > now).then_some(self.tier)
+    }
+
+    /// Grandfathered USD price for `tier`, if the lock still holds at `now` and
+    /// the subscription has not lapsed for longer than `grace_period`.
+    pub fn locked_price(&self, tier: u8, now: i64, grace_period: i64) -> Option<u64> {
+        let holds = self.tier == tier
+            && self.locked_usd_price > 0
+            && now < self.price_lock_expires_at
+            && now <= self.expires_at.saturating_add(grace_period);
+        holds.then_some(self.locked_usd_price)
+    }
+
+    /// Grandfathered price in `mint` base units, if the lock holds and
+    /// started in `mint`.
+    pub fn locked_mint_price(&self, tier: u8, mint: &Pubkey, now: i64, grace_period: i64) -> Option<u64> {
+        self.locked_price(tier, now, grace_period)
+            .filter(|_| self.locked_mint == *mint && self.locked_mint_price > 0)
+            .map(|_| self.locked_mint_price)
+    }
+
+    /// Mint the periods still running at `now` were paid in. Refunds are paid
+    /// from that mint's vault, so every running period shares one mint.
+    pub fn refundable_mint(&self, now: i64) -> Option<Pubkey> {
+        self.paid_periods
+            .iter()
+            .find(|period| period.ends_at > now)
+            .map(|period| period.mint)
+    }
+
+    /// Paid amount covering the time after `now`, before any cancellation fee
+    pub fn unused_paid(&self, now: i64) -> u64 {
+        self.paid_periods
+            .iter()
+            .map(|period| period.unused_amount(now))
+            .sum()
+    }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
-pub enum SubscriptionTier {
-    None = 0,
-    Basic = 1,
-    Pro = 2,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct PaidPeriod {
+    pub mint: Pubkey,               // Payment mint the vault received
+    pub amount: u64,                // Net amount the vault received (mint base units)
+    pub paid_at: i64,               // Unix timestamp
+    pub starts_at: i64,             // Unix timestamp, later than paid_at when prepaid
+    pub ends_at: i64,               // Unix timestamp
+}
+
+impl PaidPeriod {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 8; // 64
+
+    /// Part of `amount` paying for the time after `now`
+    pub fn unused_amount(&self, now: i64) -> u64 {
+        if self.ends_at <= now {
+            return 0;
+        }
+        let length = (self.ends_at - self.starts_at).max(1) as u128;
+        let unused = (self.ends_at - self.starts_at.max(now)) as u128;
+        (self.amount as u128 * unused / length) as u64
+    }
+}
+
+#[account]
+pub struct OrgSubscription {
+    pub owner: Pubkey,              // Team admin, pays and manages seats
+    pub tier: u8,                   // Tier granted to every seat
+    pub seats: u16,                 // Seats paid for
+    pub seats_used: u16,            // Seats assigned to members
+    pub started_at: i64,            // Unix timestamp
+    pub expires_at: i64,            // Unix timestamp, shared by all seats
+    pub total_paid: u64,            // Payments in `mint` since started_at (mint base units)
+    pub bump: u8,
+    pub mint: Pubkey,               // Payment mint of the current run
+    pub locked_usd_price: u64,      // Per-seat USD price grandfathered for this org, 0 = none
+    pub price_lock_expires_at: i64, // Unix timestamp, locked price applies until then
+    pub locked_mint: Pubkey,        // Mint the lock started in
+    pub locked_mint_price: u64,     // Per-seat price grandfathered in locked_mint
+}
+
+impl OrgSubscription {
+    pub const MAX_SEATS: u16 = 500;
+    pub const LEN: usize = 8 + 32 + 1 + 2 + 2 + 8 + 8 + 8 + 1 + 32 + 8 + 8 + 32 + 8; // 158
+
+    /// Tier an `OrgSeat` of this org can access at `now`, if any.
+    /// An active seat grants the same access as a personal `Subscription`.
+    pub fn active_tier(&self, now: i64) -> Option<u8> {
+        (self.expires_at > now).then_some(self.tier)
+    }
+
+    /// Same rule as `Subscription::locked_price`, for the org's seat price.
+    pub fn locked_price(&self, tier: u8, now: i64, grace_period: i64) -> Option<u64> {
+        let holds = self.tier == tier
+            && self.locked_usd_price > 0
+            && now < self.price_lock_expires_at
+            && now <= self.expires_at.saturating_add(grace_period);
+        holds.then_some(self.locked_usd_price)
+    }
+
+    /// Same rule as `Subscription::locked_mint_price`, for the org's seat price.
+    pub fn locked_mint_price(&self, tier: u8, mint: &Pubkey, now: i64, grace_period: i64) -> Option<u64> {
+        self.locked_price(tier, now, grace_period)
+            .filter(|_| self.locked_mint == *mint && self.locked_mint_price > 0)
+            .map(|_| self.locked_mint_price)
+    }
+}
+
+#[account]
+pub struct OrgSeat {
+    pub org: Pubkey,                // OrgSubscription this seat belongs to
+    pub member: Pubkey,             // Wallet holding the seat
+    pub added_at: i64,              // Unix timestamp
+    pub bump: u8,
+}
+
+impl OrgSeat {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1; // 81
+}
+
+#[account]
+pub struct SubscriptionPass {
+    pub mint: Pubkey,               // Supply-1 pass mint, authority is the config PDA
+    pub tier: u8,
+    pub expires_at: i64,            // Unix timestamp
+    pub created_at: i64,            // Unix timestamp
+    pub original_owner: Pubkey,     // Wallet that paid for the pass
+    pub bump: u8,
+}
+
+impl SubscriptionPass {
+    pub const LEN: usize = 8 + 32 + 1 + 8 + 8 + 32 + 1; // 90
+
+    /// Tier the current holder of the pass token can access at `now`, if any
+    pub fn active_tier(&self, now: i64) -> Option<u8> {
+        (self.expires_at > now).then_some(self.tier)
+    }
+}
+
+#[account]
+pub struct ReferrerProfile {
+    pub wallet: Pubkey,             // Referrer wallet, owns commission token accounts
+    pub referral_count: u32,        // Subscribers bound to this referrer
+    pub total_earned: u64,          // Cumulative commission (payment mint base units)
+    pub created_at: i64,            // Unix timestamp
+    pub bump: u8,
+    pub total_earned_lamports: u64, // Cumulative commission on SOL payments
+}
+
+impl ReferrerProfile {
+    pub const LEN: usize = 8 + 32 + 4 + 8 + 8 + 1 + 8; // 69
+}
+
+#[account]
+pub struct PromoCode {
+    pub code_hash: [u8; 32],        // sha256 of the code string
+    pub discount_kind: u8,          // 0 = percent (bps), 1 = fixed USD amount (6 decimals)
+    pub discount_value: u64,
+    pub allowed_tiers: u64,         // Bitmask, bit n = tier n
+    pub max_redemptions: u32,
+    pub redemptions: u32,
+    pub per_wallet_limit: u16,
+    pub expires_at: i64,            // Unix timestamp
+    pub active: bool,
+    pub created_by: Pubkey,
+    pub bump: u8,
+}
+
+impl PromoCode {
+    pub const LEN: usize = 8 + 32 + 1 + 8 + 8 + 4 + 4 + 2 + 8 + 1 + 32 + 1; // 109
+    pub const DISCOUNT_PERCENT: u8 = 0;
+    pub const DISCOUNT_FIXED: u8 = 1;
+}
+
+#[account]
+pub struct PromoRedemption {
+    pub promo_code: Pubkey,
+    pub user: Pubkey,
+    pub count: u16,                 // Times this wallet redeemed the code
+    pub bump: u8,
+}
+
+impl PromoRedemption {
+    pub const LEN: usize = 8 + 32 + 32 + 2 + 1; // 75
+}
+
+#[account]
+pub struct TrialClaim {
+    pub user: Pubkey,               // Wallet that used its trial
+    pub claimed_at: i64,            // Unix timestamp
+    pub bump: u8,
+}
+
+impl TrialClaim {
+    pub const LEN: usize = 8 + 32 + 8 + 1; // 49
+}
+
+#[account]
+pub struct AutoRenewal {
+    pub user: Pubkey,               // Subscriber that opted in
+    pub token_account: Pubkey,      // Account the renewal delegate may debit
+    pub mint: Pubkey,               // Payment mint of token_account
+    pub max_amount: u64,            // Allowance approved to the delegate
+    pub renewals: u32,              // Successful crank renewals
+    pub last_renewed_at: i64,       // Unix timestamp
+    pub bump: u8,
+    pub renewed_due_at: i64,        // Expiry the latest renewal extended
+}
+
+impl AutoRenewal {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 4 + 8 + 1 + 8; // 133
+    pub const RENEWAL_WINDOW: i64 = 3 * 24 * 60 * 60; // Renew up to 3 days before expiry
+    pub const MAX_LAPSE: i64 = 3 * 24 * 60 * 60; // No crank renewals once lapsed for 3 days
+    pub const KEEPER_TIP_BPS: u64 = 50; // 0.5% of the renewal price
+}
+
+#[account]
+pub struct GiftReceipt {
+    pub payer: Pubkey,              // Wallet that paid
+    pub recipient: Pubkey,          // Wallet whose subscription was funded
+    pub tier: u8,
+    pub amount: u64,                // Paid in `mint` base units
+    pub mint: Pubkey,
+    pub memo_hash: Option<[u8; 32]>, // Hash of an off-chain gift message
+    pub expires_at: i64,            // Recipient expiry after the gift
+    pub created_at: i64,            // Unix timestamp
+    pub bump: u8,
+}
+
+impl GiftReceipt {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 8 + 32 + (1 + 32) + 8 + 8 + 1; // 163
+}
+
+#[account]
+pub struct TierConfig {
+    pub tier_id: u8,                // 1..=MAX_TIER_ID, 0 means no subscription
+    pub name: [u8; 32],             // UTF-8, zero padded, e.g. "Pro Annual"
+    pub usd_price: u64,             // List price in USD (6 decimals) e.g., 20_000_000 = $20
+    pub duration: i64,              // Seconds per period, e.g., 2592000 = 30 days
+    pub features: u32,              // Bitmask of FEATURE_*
+    pub active: bool,               // Retired tiers can no longer be bought
+    pub bump: u8,
+    pub mint_prices: Vec<TierMintPrice>, // Admin-set prices per payment mint, max MAX_PAYMENT_MINTS
+}
+
+impl TierConfig {
+    pub const LEN: usize = 8 + 1 + 32 + 8 + 8 + 4 + 1 + 1
+        + (4 + TierMintPrice::LEN * ProgramConfig::MAX_PAYMENT_MINTS); // 227
+    pub const MAX_TIER_ID: u8 = 63; // PromoCode.allowed_tiers is a u64 bitmask
+
+    pub const FEATURE_SIGNALS: u32 = 1 << 0;
+    pub const FEATURE_WHALE_MOVEMENTS: u32 = 1 << 1;
+    pub const FEATURE_ALERTS: u32 = 1 << 2;
+    pub const FEATURE_API_ACCESS: u32 = 1 << 3;
+
+    pub fn has_feature(&self, feature: u32) -> bool {
+        self.features & feature != 0
+    }
+
+    /// Admin-set price in `mint` base units, if any. Mints without one are
+    /// priced from `usd_price`.
+    pub fn mint_price(&self, mint: &Pubkey) -> Option<u64> {
+        self.mint_prices
+            .iter()
+            .find(|entry| entry.mint == *mint)
+            .map(|entry| entry.price)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct TierMintPrice {
+    pub mint: Pubkey,               // Accepted payment mint
+    pub price: u64,                 // Price per period in `mint` base units
+}
+
+impl TierMintPrice {
+    pub const LEN: usize = 32 + 8; // 40
 }
 
 #[account]
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use prediction_copilot::instructions::access::verify_subscription_cpi;
+use prediction_copilot::program::PredictionCopilot;
+
+declare_id!("2hTwMMmD7CNBkZHNTJreZbFLNbJAKWLQLQsLBPX1E2uf");
+
+/// Minimal integration of `verify_subscription` over CPI, as a third-party
+/// program would gate its own instructions. Also exercised by the tests.
+#[program]
+pub mod verify_caller {
+    use super::*;
+
+    /// Succeeds only while `user` holds `tier_config`'s tier and that tier
+    /// includes every bit of `required_features`.
+    pub fn gated_action(ctx: Context<GatedAction>, required_features: u32) -> Result<()> {
+        let status = verify_subscription_cpi(
+            ctx.accounts.prediction_copilot_program.to_account_info(),
+            ctx.accounts.user.to_account_info(),
+            ctx.accounts.tier_config.to_account_info(),
+            ctx.accounts.subscription.as_ref().map(|subscription| subscription.to_account_info()),
+            None,
+            None,
+            None,
+            None,
+        )?;
+        if status.features & required_features != required_features {
+            return err!(CallerError::MissingFeatures);
+        }
+    
+        emit!(GatedActionAllowed {
+            user: ctx.accounts.user.key(),
+            tier: status.tier,
+            expires_at: status.expires_at,
+        });
+    
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct GatedAction<'info> {
+    pub user: Signer<'info>,
+    
+    /// CHECK: Validated by prediction-copilot
+    pub tier_config: UncheckedAccount<'info>,
+    
+    /// CHECK: Validated by prediction-copilot
+    pub subscription: Option<UncheckedAccount<'info>>,
+    
+    pub prediction_copilot_program: Program<'info, PredictionCopilot>,
+}
+
+#[event]
+pub struct GatedActionAllowed {
+    pub user: Pubkey,
+    pub tier: u8,
+    pub expires_at: i64,
+}
+
+#[error_code]
+pub enum CallerError {
+    #[msg("Subscription tier lacks a required feature")]
+    MissingFeatures,
+}
@@ -2,6 +2,41 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { PredictionCopilot } from "../target/types/prediction_copilot";
 import { assert } from "chai";
+import { TOKEN_PROGRAM_ID, createMint, createAccount } from "@solana/spl-token";
+
+const BPF_LOADER_UPGRADEABLE_ID = new anchor.web3.PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
+);
+
+// Role ids, see state.rs `Role`
+const ROLE_PUBLISHER = 1;
+const ROLE_CURATOR = 3;
+
+// Pause bits, see state.rs `ProgramConfig::PAUSE_*`
+const PAUSE_SIGNALS = 1 << 1;
+
+export function tierPda(programId: anchor.web3.PublicKey, tierId: number) {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("tier"), Buffer.from([tierId])],
+    programId
+  )[0];
+}
+
+export function tierName(name: string) {
+  const buf = Buffer.alloc(32);
+  buf.write(name);
+  return Array.from(buf);
+}
+
+export async function expectError(promise: Promise<unknown>, code: string) {
+  try {
+    await promise;
+  } catch (e) {
+    assert.equal(e.error?.errorCode?.code, code);
+    return;
+  }
+  assert.fail(`expected ${code}`);
+}
 
 describe("admin-actions", () => {
   // Configure the client to use the local cluster.
@@ -9,42 +44,106 @@ describe("admin-actions", () => {
   anchor.setProvider(provider);
 
   const program = anchor.workspace.PredictionCopilot as Program<PredictionCopilot>;
-  
+  const payer = (provider.wallet as any).payer; // Access underlying keypair for spl-token funcs
+
   // PDAs
   const [configPda] = anchor.web3.PublicKey.findProgramAddressSync(
     [Buffer.from("config")],
     program.programId
   );
-  
+
   const [registryPda] = anchor.web3.PublicKey.findProgramAddressSync(
     [Buffer.from("registry")],
     program.programId
   );
 
+  const [adminLogPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("admin_log")],
+    program.programId
+  );
+
+  const [programDataPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    BPF_LOADER_UPGRADEABLE_ID
+  );
+
+  const [multisigPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("multisig")],
+    program.programId
+  );
+
+  const [pendingConfigPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("pending_config")],
+    program.programId
+  );
+
+  function rolePda(role: number, holder: anchor.web3.PublicKey) {
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), Buffer.from([role]), holder.toBuffer()],
+      program.programId
+    )[0];
+  }
+
+  async function grantRole(role: number, holder: anchor.web3.PublicKey) {
+    const roleGrant = rolePda(role, holder);
+    if (await provider.connection.getAccountInfo(roleGrant)) {
+      return roleGrant;
+    }
+    await program.methods
+      .grantRole(role, holder)
+      .accounts({
+        admin: provider.wallet.publicKey,
+        config: configPda,
+        roleGrant,
+        proposal: null,
+        adminLog: adminLogPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    return roleGrant;
+  }
+
+  function proposalPda(index: anchor.BN) {
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), multisigPda.toBuffer(), index.toArrayLike(Buffer, 'le', 8)],
+      program.programId
+    )[0];
+  }
+
   it("Is initialized!", async () => {
-    // Basic Price: 5 USDC (5_000_000)
-    // Pro Price: 20 USDC (20_000_000)
-    // Duration: 30 days (2592000 seconds)
-    
-    // Create a random treasury keypair for testing
-    const treasury = anchor.web3.Keypair.generate();
+    // The deployer's wallet is the upgrade authority on localnet
+    // USDC-like payment mint (6 decimals), minted by the provider wallet
+    const usdcMint = await createMint(
+        provider.connection,
+        payer,
+        provider.wallet.publicKey,
+        null,
+        6
+    );
+
+    // Treasury must be a token account for the payment mint
+    const treasury = await createAccount(
+        provider.connection,
+        payer,
+        usdcMint,
+        provider.wallet.publicKey,
+        anchor.web3.Keypair.generate()
+    );
 
     try {
         await program.methods
-        .initializeConfig(
-            new anchor.BN(5_000_000), 
-            new anchor.BN(20_000_000),
-            new anchor.BN(2592000),
-            new anchor.BN(2592000)
-        )
+        .initializeConfig()
         .accounts({
             admin: provider.wallet.publicKey,
             config: configPda,
-            treasury: treasury.publicKey,
+            treasury,
+            paymentMint: usdcMint,
+            program: program.programId,
+            programData: programDataPda,
             systemProgram: anchor.web3.SystemProgram.programId,
         })
         .rpc();
-        
+
         console.log("Config initialized");
     } catch (e) {
         console.log("Config might already be initialized:", e);
@@ -52,9 +151,175 @@ describe("admin-actions", () => {
 
     const configAccount = await program.account.programConfig.fetch(configPda);
     assert.ok(configAccount.admin.equals(provider.wallet.publicKey));
-    assert.equal(configAccount.basicPrice.toNumber(), 5_000_000);
+    assert.equal(configAccount.paymentMints.length, 1);
+    assert.equal(configAccount.paymentMints[0].decimals, 6);
   });
-  
+
+  it("Initializes Admin Log and Vault", async () => {
+      try {
+        await program.methods
+            .initializeAdminLog()
+            .accounts({
+                admin: provider.wallet.publicKey,
+                config: configPda,
+                proposal: null,
+                adminLog: adminLogPda,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .rpc();
+        console.log("Admin log initialized");
+      } catch (e) {
+          console.log("Admin log might already be initialized", e);
+      }
+
+      const configAccount = await program.account.programConfig.fetch(configPda);
+      const usdcMint = configAccount.paymentMints[0].mint;
+      const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), usdcMint.toBuffer()],
+        program.programId
+      );
+      const [vaultLedger] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault_ledger"), usdcMint.toBuffer()],
+        program.programId
+      );
+
+      try {
+        await program.methods
+            .initializeVault()
+            .accounts({
+                payer: provider.wallet.publicKey,
+                config: configPda,
+                mint: usdcMint,
+                vault: vaultPda,
+                vaultLedger,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            })
+            .rpc();
+        console.log("Vault initialized");
+      } catch (e) {
+          console.log("Vault might already be initialized", e);
+      }
+
+      assert.ok(await provider.connection.getAccountInfo(vaultPda));
+  });
+
+  it("Creates Basic and Pro Tiers", async () => {
+      // Basic: 5 USD / 30 days, Pro: 20 USD / 30 days
+      const tiers = [
+          { id: 1, name: "Basic", usdPrice: 5_000_000, features: 0b0001 },
+          { id: 2, name: "Pro", usdPrice: 20_000_000, features: 0b1111 },
+      ];
+
+      for (const tier of tiers) {
+          const tierConfig = tierPda(program.programId, tier.id);
+          if (await provider.connection.getAccountInfo(tierConfig)) {
+              continue;
+          }
+          await program.methods
+            .createTier(tier.id, tierName(tier.name), new anchor.BN(tier.usdPrice), new anchor.BN(2592000), tier.features)
+            .accounts({
+                admin: provider.wallet.publicKey,
+                config: configPda,
+                tierConfig,
+                proposal: null,
+                adminLog: adminLogPda,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .rpc();
+      }
+
+      const pro = await program.account.tierConfig.fetch(tierPda(program.programId, 2));
+      assert.equal(pro.tierId, 2);
+      assert.equal(pro.usdPrice.toNumber(), 20_000_000);
+      assert.equal(pro.duration.toNumber(), 2592000);
+      assert.equal(pro.features, 0b1111);
+      assert.ok(pro.active);
+  });
+
+  it("Rejects Tier Ids Out of Range", async () => {
+      await expectError(
+        program.methods
+            .createTier(64, tierName("Too High"), new anchor.BN(1_000_000), new anchor.BN(86400), 0)
+            .accounts({
+                admin: provider.wallet.publicKey,
+                config: configPda,
+                tierConfig: tierPda(program.programId, 64),
+                proposal: null,
+                adminLog: adminLogPda,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .rpc(),
+        "InvalidTier"
+      );
+  });
+
+  it("Retires a Tier", async () => {
+      const tierConfig = tierPda(program.programId, 9);
+      if (!(await provider.connection.getAccountInfo(tierConfig))) {
+          await program.methods
+            .createTier(9, tierName("Legacy"), new anchor.BN(1_000_000), new anchor.BN(86400), 0)
+            .accounts({
+                admin: provider.wallet.publicKey,
+                config: configPda,
+                tierConfig,
+                proposal: null,
+                adminLog: adminLogPda,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .rpc();
+      }
+
+      const retire = () =>
+        program.methods
+            .retireTier(9)
+            .accounts({
+                admin: provider.wallet.publicKey,
+                config: configPda,
+                tierConfig,
+                proposal: null,
+                adminLog: adminLogPda,
+            })
+            .rpc();
+
+      await retire();
+      const tierAccount = await program.account.tierConfig.fetch(tierConfig);
+      assert.ok(!tierAccount.active);
+
+      // A retired tier stays readable but cannot be retired again
+      await expectError(retire(), "TierInactive");
+  });
+
+  it("Requires the Timelock for Price Increases", async () => {
+      const tierConfig = tierPda(program.programId, 1);
+      const update = (usdPrice: number | null, duration: number | null, features: number | null) =>
+        program.methods
+            .updateTier(
+                1,
+                usdPrice === null ? null : new anchor.BN(usdPrice),
+                duration === null ? null : new anchor.BN(duration),
+                features
+            )
+            .accounts({
+                admin: provider.wallet.publicKey,
+                config: configPda,
+                tierConfig,
+                proposal: null,
+                adminLog: adminLogPda,
+            })
+            .rpc();
+
+      await expectError(update(6_000_000, null, null), "PriceIncreaseRequiresTimelock");
+      await expectError(update(null, 86400, null), "PriceIncreaseRequiresTimelock");
+      await expectError(update(null, null, 0), "PriceIncreaseRequiresTimelock");
+
+      // Changes in the subscriber's favour apply at once
+      await update(null, null, 0b0011);
+      const tierAccount = await program.account.tierConfig.fetch(tierConfig);
+      assert.equal(tierAccount.features, 0b0011);
+  });
+
   it("Initializes Registry", async () => {
       try {
         await program.methods
@@ -62,7 +327,11 @@ describe("admin-actions", () => {
             .accounts({
                 admin: provider.wallet.publicKey,
                 config: configPda,
+                proposal: null,
                 registry: registryPda,
+                program: program.programId,
+                programData: programDataPda,
+                adminLog: adminLogPda,
                 systemProgram: anchor.web3.SystemProgram.programId,
             })
             .rpc();
@@ -70,28 +339,30 @@ describe("admin-actions", () => {
       } catch (e) {
           console.log("Registry might already be initialized", e);
       }
-      
+
       const registryAccount = await program.account.whaleRegistry.fetch(registryPda);
       assert.equal(registryAccount.whaleCount, 0);
   });
-  
+
   it("Adds a Trader", async () => {
       // Mock polygon address (20 bytes)
       const polygonAddress = Array.from(Buffer.alloc(20, 1)); // [1, 1, ..., 1]
-      
+
       const [traderPda] = anchor.web3.PublicKey.findProgramAddressSync(
         [Buffer.from("trader"), Buffer.from(polygonAddress)],
         program.programId
       );
-      
+
       try {
           // Check if exists first to avoid error in reruns
           await program.account.trackedTrader.fetch(traderPda);
           console.log("Trader already exists");
       } catch (e) {
+          const roleGrant = await grantRole(ROLE_CURATOR, provider.wallet.publicKey);
           await program.methods
             .addTrader(
                 polygonAddress,
+                null, // No Solana address
                 0, // Whale tier
                 new anchor.BN(1000000), // Total PnL (1 USDC)
                 7500, // Win rate 75%
@@ -102,33 +373,37 @@ describe("admin-actions", () => {
             .accounts({
                 admin: provider.wallet.publicKey,
                 config: configPda,
+                roleGrant,
+                proposal: null,
                 registry: registryPda,
                 trader: traderPda,
+                adminLog: adminLogPda,
                 systemProgram: anchor.web3.SystemProgram.programId,
             })
             .rpc();
             console.log("Trader added");
       }
-      
+
       const traderAccount = await program.account.trackedTrader.fetch(traderPda);
       assert.deepEqual(traderAccount.polygonAddress, polygonAddress);
       assert.equal(traderAccount.tier, 0);
   });
-  
+
   it("Publishes a Signal", async () => {
       const asset = Buffer.alloc(16);
       asset.write("BTC/USD");
       const assetArray = Array.from(asset);
       const detectedAt = new anchor.BN(Math.floor(Date.now() / 1000));
-      
+
       const [signalPda] = anchor.web3.PublicKey.findProgramAddressSync(
         [Buffer.from("signal"), Buffer.from(assetArray), detectedAt.toArrayLike(Buffer, 'le', 8)],
         program.programId
       );
-      
+
       // Random feed address
       const pythFeed = anchor.web3.Keypair.generate().publicKey;
-      
+      const roleGrant = await grantRole(ROLE_PUBLISHER, provider.wallet.publicKey);
+
       await program.methods
         .publishSignal(
             assetArray,
@@ -142,15 +417,256 @@ describe("admin-actions", () => {
         .accounts({
             publisher: provider.wallet.publicKey,
             config: configPda,
+            roleGrant,
             signal: signalPda,
             pythFeed: pythFeed,
             systemProgram: anchor.web3.SystemProgram.programId,
         })
         .rpc();
-        
+
       console.log("Signal published");
-      
+
       const signalAccount = await program.account.pythSignal.fetch(signalPda);
       assert.equal(signalAccount.multiplier, 300);
   });
+
+  it("Executes an Admin Action Through the Multisig", async () => {
+      // 2-of-2 multisig, neither signer is the config admin
+      const signerA = anchor.web3.Keypair.generate();
+      const signerB = anchor.web3.Keypair.generate();
+      const sx = await provider.connection.requestAirdrop(signerA.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sx);
+
+      await program.methods
+        .createMultisig([signerA.publicKey, signerB.publicKey], 2)
+        .accounts({
+            admin: provider.wallet.publicKey,
+            config: configPda,
+            proposal: null,
+            multisig: multisigPda,
+            adminLog: adminLogPda,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      const { proposalCount } = await program.account.multisig.fetch(multisigPda);
+      const proposal = proposalPda(proposalCount);
+      await program.methods
+        .createProposal({ setPauseFlags: { pauseFlags: 0 } })
+        .accounts({
+            proposer: signerA.publicKey,
+            multisig: multisigPda,
+            proposal,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([signerA])
+        .rpc();
+
+      const execute = () =>
+        program.methods
+            .setPauseFlags(0)
+            .accounts({
+                admin: signerA.publicKey,
+                config: configPda,
+                proposal,
+                adminLog: adminLogPda,
+            })
+            .signers([signerA])
+            .rpc();
+
+      // The proposer's approval alone is below the threshold
+      await expectError(execute(), "ProposalNotApproved");
+
+      await program.methods
+        .approveProposal()
+        .accounts({
+            signer: signerB.publicKey,
+            multisig: multisigPda,
+            proposal,
+        })
+        .signers([signerB])
+        .rpc();
+
+      await execute();
+      const proposalAccount = await program.account.multisigProposal.fetch(proposal);
+      assert.ok(proposalAccount.approved);
+      assert.ok(proposalAccount.executed);
+
+      // An executed proposal cannot be replayed
+      await expectError(execute(), "ProposalAlreadyExecuted");
+  });
+
+  it("Hands Over the Admin in Two Steps", async () => {
+      const newAdmin = anchor.web3.Keypair.generate();
+      const propose = (admin: anchor.web3.Keypair | null, pending: anchor.web3.PublicKey) =>
+        program.methods
+            .proposeAdmin(pending)
+            .accounts({
+                admin: admin ? admin.publicKey : provider.wallet.publicKey,
+                config: configPda,
+                proposal: null,
+                adminLog: adminLogPda,
+            })
+            .signers(admin ? [admin] : [])
+            .rpc();
+      const accept = (admin: anchor.web3.Keypair | null) =>
+        program.methods
+            .acceptAdmin()
+            .accounts({
+                newAdmin: admin ? admin.publicKey : provider.wallet.publicKey,
+                config: configPda,
+                registry: registryPda,
+                adminLog: adminLogPda,
+            })
+            .signers(admin ? [admin] : [])
+            .rpc();
+
+      await propose(null, newAdmin.publicKey);
+
+      // Only the proposed wallet can take over
+      await expectError(accept(anchor.web3.Keypair.generate()), "Unauthorized");
+
+      await accept(newAdmin);
+      const configAccount = await program.account.programConfig.fetch(configPda);
+      const registryAccount = await program.account.whaleRegistry.fetch(registryPda);
+      assert.ok(configAccount.admin.equals(newAdmin.publicKey));
+      assert.ok(registryAccount.admin.equals(newAdmin.publicKey));
+      assert.isNull(configAccount.pendingAdmin);
+
+      // Hand it back for the remaining tests
+      await propose(newAdmin, provider.wallet.publicKey);
+      await accept(null);
+      assert.ok((await program.account.programConfig.fetch(configPda)).admin.equals(provider.wallet.publicKey));
+  });
+
+  it("Pauses Signals Without Touching Other Subsystems", async () => {
+      const setPauseFlags = (pauseFlags: number) =>
+        program.methods
+            .setPauseFlags(pauseFlags)
+            .accounts({
+                admin: provider.wallet.publicKey,
+                config: configPda,
+                proposal: null,
+                adminLog: adminLogPda,
+            })
+            .rpc();
+
+      const asset = Buffer.alloc(16);
+      asset.write("ETH/USD");
+      const assetArray = Array.from(asset);
+      const roleGrant = await grantRole(ROLE_PUBLISHER, provider.wallet.publicKey);
+      const publish = (detectedAt: anchor.BN) =>
+        program.methods
+            .publishSignal(assetArray, detectedAt, new anchor.BN(3000000000), new anchor.BN(100), new anchor.BN(50), 200, 0)
+            .accounts({
+                publisher: provider.wallet.publicKey,
+                config: configPda,
+                roleGrant,
+                signal: anchor.web3.PublicKey.findProgramAddressSync(
+                    [Buffer.from("signal"), asset, detectedAt.toArrayLike(Buffer, 'le', 8)],
+                    program.programId
+                )[0],
+                pythFeed: anchor.web3.Keypair.generate().publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .rpc();
+
+      // Bits outside PAUSE_ALL are rejected
+      await expectError(setPauseFlags(1 << 4), "InvalidPauseFlags");
+
+      await setPauseFlags(PAUSE_SIGNALS);
+      const detectedAt = new anchor.BN(Math.floor(Date.now() / 1000));
+      await expectError(publish(detectedAt), "ProgramPaused");
+
+      // The registry is a separate subsystem and stays open
+      const polygonAddress = Array.from(Buffer.alloc(20, 1));
+      await program.methods
+        .updateTrader(
+            polygonAddress,
+            0,
+            new anchor.BN(2000000),
+            7600,
+            101,
+            new anchor.BN(6000000),
+            600,
+            detectedAt
+        )
+        .accounts({
+            admin: provider.wallet.publicKey,
+            config: configPda,
+            roleGrant: rolePda(ROLE_CURATOR, provider.wallet.publicKey),
+            proposal: null,
+            trader: anchor.web3.PublicKey.findProgramAddressSync(
+                [Buffer.from("trader"), Buffer.from(polygonAddress)],
+                program.programId
+            )[0],
+            registry: registryPda,
+            adminLog: adminLogPda,
+        })
+        .rpc();
+
+      await setPauseFlags(0);
+      await publish(detectedAt);
+      const signalAccount = await program.account.pythSignal.fetch(
+        anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("signal"), asset, detectedAt.toArrayLike(Buffer, 'le', 8)],
+            program.programId
+        )[0]
+      );
+      assert.equal(signalAccount.multiplier, 200);
+  });
+
+  it("Holds Queued Changes Until the Timelock Elapses", async () => {
+      const tierConfig = tierPda(program.programId, 1);
+      const queue = (eta: number) =>
+        program.methods
+            .queueConfigChange(1, new anchor.BN(6_000_000), null, null, null, null, new anchor.BN(eta))
+            .accounts({
+                admin: provider.wallet.publicKey,
+                config: configPda,
+                pendingChange: pendingConfigPda,
+                treasury: null,
+                proposal: null,
+                adminLog: adminLogPda,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .rpc();
+
+      const now = await provider.connection.getBlockTime(await provider.connection.getSlot());
+      await expectError(queue(now + 24 * 60 * 60), "TimelockTooShort");
+      await queue(now + 7 * 24 * 60 * 60 + 60);
+
+      // Anyone may execute, but only once the notice period is over
+      await expectError(
+        program.methods
+            .executeConfigChange()
+            .accounts({
+                executor: provider.wallet.publicKey,
+                config: configPda,
+                pendingChange: pendingConfigPda,
+                queuedBy: provider.wallet.publicKey,
+                tierConfig,
+                treasury: null,
+                adminLog: adminLogPda,
+            })
+            .rpc(),
+        "TimelockNotElapsed"
+      );
+
+      await program.methods
+        .cancelConfigChange()
+        .accounts({
+            admin: provider.wallet.publicKey,
+            config: configPda,
+            pendingChange: pendingConfigPda,
+            queuedBy: provider.wallet.publicKey,
+            proposal: null,
+            adminLog: adminLogPda,
+        })
+        .rpc();
+
+      assert.isNull(await provider.connection.getAccountInfo(pendingConfigPda));
+      const tierAccount = await program.account.tierConfig.fetch(tierConfig);
+      assert.equal(tierAccount.usdPrice.toNumber(), 5_000_000);
+  });
 });
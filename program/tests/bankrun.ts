@@ -0,0 +1,512 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PredictionCopilot } from "../target/types/prediction_copilot";
+import { assert } from "chai";
+import { createHash } from "crypto";
+import { readFileSync } from "fs";
+import { start, AddedAccount, Clock, ProgramTestContext } from "solana-bankrun";
+import { TOKEN_PROGRAM_ID, ACCOUNT_SIZE, AccountLayout, MINT_SIZE, MintLayout } from "@solana/spl-token";
+import { tierName, tierPda } from "./admin-actions";
+
+// Flows a local validator cannot reach: moving the clock forward, oracle
+// accounts and accounts in layouts the current program no longer writes.
+
+const DAY = 24 * 60 * 60;
+
+const BPF_LOADER_UPGRADEABLE_ID = new anchor.web3.PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
+);
+
+const PYTH_RECEIVER_ID = new anchor.web3.PublicKey(
+  "rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ"
+);
+
+// See state.rs `ProgramConfig::SOL_USD_FEED_ID`
+const SOL_USD_FEED_ID = Buffer.from(
+  "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
+  "hex"
+);
+
+describe("bankrun", () => {
+  const program = anchor.workspace.PredictionCopilot as Program<PredictionCopilot>;
+  const programId = program.programId;
+
+  const [configPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("config")],
+    programId
+  );
+
+  const [adminLogPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("admin_log")],
+    programId
+  );
+
+  const [programDataPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [programId.toBuffer()],
+    BPF_LOADER_UPGRADEABLE_ID
+  );
+
+  function account(owner: anchor.web3.PublicKey, data: Buffer, lamports = anchor.web3.LAMPORTS_PER_SOL) {
+    return { lamports, data, owner, executable: false };
+  }
+
+  function wallet(publicKey: anchor.web3.PublicKey): AddedAccount {
+    return {
+      address: publicKey,
+      info: account(anchor.web3.SystemProgram.programId, Buffer.alloc(0), 100 * anchor.web3.LAMPORTS_PER_SOL),
+    };
+  }
+
+  function mint(address: anchor.web3.PublicKey, decimals: number): AddedAccount {
+    const data = Buffer.alloc(MINT_SIZE);
+    MintLayout.encode(
+      {
+        mintAuthorityOption: 0,
+        mintAuthority: anchor.web3.PublicKey.default,
+        supply: BigInt(0),
+        decimals,
+        isInitialized: true,
+        freezeAuthorityOption: 0,
+        freezeAuthority: anchor.web3.PublicKey.default,
+      },
+      data
+    );
+    return { address, info: account(TOKEN_PROGRAM_ID, data) };
+  }
+
+  function tokenAccount(
+    address: anchor.web3.PublicKey,
+    mintAddress: anchor.web3.PublicKey,
+    owner: anchor.web3.PublicKey,
+    amount = 0
+  ): AddedAccount {
+    const data = Buffer.alloc(ACCOUNT_SIZE);
+    AccountLayout.encode(
+      {
+        mint: mintAddress,
+        owner,
+        amount: BigInt(amount),
+        delegateOption: 0,
+        delegate: anchor.web3.PublicKey.default,
+        state: 1, // Initialized
+        isNativeOption: 0,
+        isNative: BigInt(0),
+        delegatedAmount: BigInt(0),
+        closeAuthorityOption: 0,
+        closeAuthority: anchor.web3.PublicKey.default,
+      },
+      data
+    );
+    return { address, info: account(TOKEN_PROGRAM_ID, data) };
+  }
+
+  // Fully verified SOL/USD PriceUpdateV2, as posted by the Pyth receiver
+  function priceUpdate(
+    address: anchor.web3.PublicKey,
+    price: number,
+    conf: number,
+    exponent: number,
+    publishTime: number
+  ): AddedAccount {
+    const data = Buffer.alloc(8 + 32 + 2 + 32 + 8 + 8 + 4 + 8 + 8 + 8 + 8 + 8);
+    createHash("sha256").update("account:PriceUpdateV2").digest().copy(data, 0, 0, 8);
+    // write_authority stays zeroed
+    data.writeUInt8(1, 40); // VerificationLevel::Full
+    SOL_USD_FEED_ID.copy(data, 41);
+    data.writeBigInt64LE(BigInt(price), 73);
+    data.writeBigUInt64LE(BigInt(conf), 81);
+    data.writeInt32LE(exponent, 89);
+    data.writeBigInt64LE(BigInt(publishTime), 93);
+    data.writeBigInt64LE(BigInt(publishTime), 101); // prev_publish_time
+    data.writeBigInt64LE(BigInt(price), 109); // ema_price
+    data.writeBigUInt64LE(BigInt(conf), 117); // ema_conf
+    return { address, info: account(PYTH_RECEIVER_ID, data) };
+  }
+
+  // Deploys the program as upgradeable with `admin` as its upgrade authority,
+  // which initialize_config and migrate_config require
+  async function startProgram(admin: anchor.web3.PublicKey, accounts: AddedAccount[]) {
+    const elf = readFileSync("target/deploy/prediction_copilot.so");
+
+    // UpgradeableLoaderState::Program { programdata_address }
+    const programAccount = Buffer.alloc(4 + 32);
+    programAccount.writeUInt32LE(2, 0);
+    programDataPda.toBuffer().copy(programAccount, 4);
+
+    // UpgradeableLoaderState::ProgramData { slot, upgrade_authority_address } + ELF
+    const programDataAccount = Buffer.alloc(4 + 8 + 1 + 32 + elf.length);
+    programDataAccount.writeUInt32LE(3, 0);
+    programDataAccount.writeUInt8(1, 12);
+    admin.toBuffer().copy(programDataAccount, 13);
+    elf.copy(programDataAccount, 45);
+
+    const context = await start([], [
+      {
+        address: programId,
+        info: { ...account(BPF_LOADER_UPGRADEABLE_ID, programAccount), executable: true },
+      },
+      {
+        address: programDataPda,
+        info: account(BPF_LOADER_UPGRADEABLE_ID, programDataAccount, 100 * anchor.web3.LAMPORTS_PER_SOL),
+      },
+      wallet(admin),
+      ...accounts,
+    ]);
+    // Programs become visible the slot after their deployment slot
+    const { slot } = await context.banksClient.getClock();
+    context.warpToSlot(slot + BigInt(1));
+    return context;
+  }
+
+  async function send(
+    context: ProgramTestContext,
+    instructions: anchor.web3.TransactionInstruction[],
+    signers: anchor.web3.Keypair[]
+  ) {
+    const tx = new anchor.web3.Transaction();
+    [tx.recentBlockhash] = await context.banksClient.getLatestBlockhash();
+    tx.feePayer = signers[0].publicKey;
+    tx.add(...instructions);
+    tx.sign(...signers);
+    return context.banksClient.tryProcessTransaction(tx);
+  }
+
+  async function expectSuccess(
+    context: ProgramTestContext,
+    instructions: anchor.web3.TransactionInstruction[],
+    signers: anchor.web3.Keypair[]
+  ) {
+    const { result, meta } = await send(context, instructions, signers);
+    assert.isNull(result, meta?.logMessages.join("\n"));
+  }
+
+  async function expectError(
+    context: ProgramTestContext,
+    instructions: anchor.web3.TransactionInstruction[],
+    signers: anchor.web3.Keypair[],
+    code: string
+  ) {
+    const { result, meta } = await send(context, instructions, signers);
+    assert.isNotNull(result, `expected ${code}`);
+    assert.ok(
+      meta?.logMessages.some((log) => log.includes(`Error Code: ${code}.`)),
+      `expected ${code}, got:\n${meta?.logMessages.join("\n")}`
+    );
+  }
+
+  async function fetch(context: ProgramTestContext, name: string, address: anchor.web3.PublicKey) {
+    const info = await context.banksClient.getAccount(address);
+    return info && program.coder.accounts.decode(name, Buffer.from(info.data));
+  }
+
+  async function now(context: ProgramTestContext) {
+    return Number((await context.banksClient.getClock()).unixTimestamp);
+  }
+
+  // Moves the clock `seconds` ahead, on a new slot
+  async function timeTravel(context: ProgramTestContext, seconds: number) {
+    const clock = await context.banksClient.getClock();
+    const slot = clock.slot + BigInt(1);
+    context.warpToSlot(slot);
+    context.setClock(
+      new Clock(
+        slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        clock.unixTimestamp + BigInt(seconds)
+      )
+    );
+  }
+
+  // Config with a 6-decimal payment mint, the admin log and a 5 USD Basic tier
+  async function startInitialized(accounts: AddedAccount[] = []) {
+    const admin = anchor.web3.Keypair.generate();
+    const usdcMint = anchor.web3.Keypair.generate().publicKey;
+    const treasuryWallet = anchor.web3.Keypair.generate().publicKey;
+    const treasury = anchor.web3.Keypair.generate().publicKey;
+    const context = await startProgram(admin.publicKey, [
+      mint(usdcMint, 6),
+      wallet(treasuryWallet),
+      tokenAccount(treasury, usdcMint, treasuryWallet),
+      ...accounts,
+    ]);
+
+    await expectSuccess(context, [
+      await program.methods
+        .initializeConfig()
+        .accounts({
+            admin: admin.publicKey,
+            config: configPda,
+            treasury,
+            paymentMint: usdcMint,
+            program: programId,
+            programData: programDataPda,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .instruction(),
+      await program.methods
+        .initializeAdminLog()
+        .accounts({
+            admin: admin.publicKey,
+            config: configPda,
+            proposal: null,
+            adminLog: adminLogPda,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .instruction(),
+      await program.methods
+        .createTier(1, tierName("Basic"), new anchor.BN(5_000_000), new anchor.BN(30 * DAY), 0b0001)
+        .accounts({
+            admin: admin.publicKey,
+            config: configPda,
+            tierConfig: tierPda(programId, 1),
+            proposal: null,
+            adminLog: adminLogPda,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .instruction(),
+    ], [admin]);
+
+    return { context, admin, usdcMint, treasury, treasuryWallet };
+  }
+
+  it("Executes a Queued Change Once the Timelock Elapses", async () => {
+      const { context, admin } = await startInitialized();
+      const [pendingConfigPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("pending_config")],
+        programId
+      );
+
+      // Raise Basic to 6 USD with the minimum 7 days notice
+      await expectSuccess(context, [
+        await program.methods
+            .queueConfigChange(1, new anchor.BN(6_000_000), null, null, null, null, new anchor.BN(await now(context) + 7 * DAY))
+            .accounts({
+                admin: admin.publicKey,
+                config: configPda,
+                pendingChange: pendingConfigPda,
+                treasury: null,
+                proposal: null,
+                adminLog: adminLogPda,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .instruction(),
+      ], [admin]);
+
+      const execute = async (executor: anchor.web3.Keypair) => [
+        await program.methods
+            .executeConfigChange()
+            .accounts({
+                executor: executor.publicKey,
+                config: configPda,
+                pendingChange: pendingConfigPda,
+                queuedBy: admin.publicKey,
+                tierConfig: tierPda(programId, 1),
+                treasury: null,
+                adminLog: adminLogPda,
+            })
+            .instruction(),
+      ];
+
+      await timeTravel(context, 7 * DAY - 60);
+      await expectError(context, await execute(admin), [admin], "TimelockNotElapsed");
+
+      // Once due anyone can execute, the rent goes back to the admin that queued it
+      const executor = anchor.web3.Keypair.generate();
+      context.setAccount(executor.publicKey, wallet(executor.publicKey).info);
+      await timeTravel(context, 60);
+      await expectSuccess(context, await execute(executor), [executor]);
+
+      const tierAccount = await fetch(context, "TierConfig", tierPda(programId, 1));
+      assert.equal(tierAccount.usdPrice.toNumber(), 6_000_000);
+      assert.isNull(await context.banksClient.getAccount(pendingConfigPda));
+  });
+
+  it("Charges SOL at the Pyth Price Within the Slippage Bound", async () => {
+      const { context, treasury, treasuryWallet } = await startInitialized();
+      const user = anchor.web3.Keypair.generate();
+      context.setAccount(user.publicKey, wallet(user.publicKey).info);
+
+      // 150 USD per SOL, 0.5 USD confidence
+      const priceUpdateAddress = anchor.web3.Keypair.generate().publicKey;
+      const { info } = priceUpdate(priceUpdateAddress, 150_00000000, 50000000, -8, await now(context));
+      context.setAccount(priceUpdateAddress, info);
+
+      const [subscription] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("subscription"), user.publicKey.toBuffer()],
+        programId
+      );
+      const subscribeWithSol = async (maxLamports: number) => [
+        await program.methods
+            .subscribeWithSol(1, new anchor.BN(maxLamports))
+            .accounts({
+                user: user.publicKey,
+                subscription,
+                config: configPda,
+                tierConfig: tierPda(programId, 1),
+                priceUpdate: priceUpdateAddress,
+                treasury,
+                treasuryWallet,
+                referrerProfile: null,
+                referrerWallet: null,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .instruction(),
+      ];
+
+      // 5 USD at 150 USD/SOL is 33_333_333.3 lamports, rounded up
+      await expectError(context, await subscribeWithSol(33_333_333), [user], "SlippageExceeded");
+
+      const treasuryBefore = (await context.banksClient.getAccount(treasuryWallet)).lamports;
+      await expectSuccess(context, await subscribeWithSol(33_333_334), [user]);
+      const treasuryAfter = (await context.banksClient.getAccount(treasuryWallet)).lamports;
+      assert.equal(Number(treasuryAfter) - Number(treasuryBefore), 33_333_334);
+
+      // Lamports go straight to the treasury wallet and are never refundable
+      const subAccount = await fetch(context, "Subscription", subscription);
+      assert.equal(subAccount.tier, 1);
+      assert.equal(subAccount.totalPaidLamports.toNumber(), 33_333_334);
+      assert.equal(subAccount.totalPaid.toNumber(), 0);
+      assert.equal(subAccount.paidPeriods.length, 0);
+  });
+
+  it("Migrates the Legacy Config Into Basic and Pro Tiers", async () => {
+      const admin = anchor.web3.Keypair.generate();
+      const stranger = anchor.web3.Keypair.generate();
+      const usdcMint = anchor.web3.Keypair.generate().publicKey;
+      const treasuryWallet = anchor.web3.Keypair.generate().publicKey;
+      const treasury = anchor.web3.Keypair.generate().publicKey;
+      const [, configBump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("config")],
+        programId
+      );
+
+      // ProgramConfig as first deployed: admin, treasury, Basic and Pro
+      // prices and durations, paused, bump
+      const legacyConfig = Buffer.alloc(106);
+      createHash("sha256").update("account:ProgramConfig").digest().copy(legacyConfig, 0, 0, 8);
+      admin.publicKey.toBuffer().copy(legacyConfig, 8);
+      treasuryWallet.toBuffer().copy(legacyConfig, 40);
+      legacyConfig.writeBigUInt64LE(BigInt(5_000_000), 72);
+      legacyConfig.writeBigUInt64LE(BigInt(20_000_000), 80);
+      legacyConfig.writeBigInt64LE(BigInt(30 * DAY), 88);
+      legacyConfig.writeBigInt64LE(BigInt(30 * DAY), 96);
+      legacyConfig.writeUInt8(0, 104);
+      legacyConfig.writeUInt8(configBump, 105);
+
+      const context = await startProgram(admin.publicKey, [
+        mint(usdcMint, 6),
+        wallet(treasuryWallet),
+        tokenAccount(treasury, usdcMint, treasuryWallet),
+        wallet(stranger.publicKey),
+        { address: configPda, info: account(programId, legacyConfig) },
+      ]);
+
+      const migrate = async (signer: anchor.web3.Keypair) => [
+        await program.methods
+            .migrateConfig(0b0001, 0b1111)
+            .accounts({
+                admin: signer.publicKey,
+                config: configPda,
+                basicTier: tierPda(programId, 1),
+                proTier: tierPda(programId, 2),
+                treasury,
+                paymentMint: usdcMint,
+                program: programId,
+                programData: programDataPda,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .instruction(),
+      ];
+
+      // Only the upgrade authority can migrate
+      await expectError(context, await migrate(stranger), [stranger], "Unauthorized");
+      await expectSuccess(context, await migrate(admin), [admin]);
+
+      const configAccount = await fetch(context, "ProgramConfig", configPda);
+      assert.ok(configAccount.admin.equals(admin.publicKey));
+      assert.ok(configAccount.treasury.equals(treasury));
+      assert.equal(configAccount.paymentMints.length, 1);
+      assert.ok(configAccount.paymentMints[0].mint.equals(usdcMint));
+      assert.equal(configAccount.paymentMints[0].decimals, 6);
+      assert.equal(configAccount.pauseFlags, 0);
+
+      const basicTier = await fetch(context, "TierConfig", tierPda(programId, 1));
+      assert.deepEqual(basicTier.name, tierName("Basic"));
+      assert.equal(basicTier.usdPrice.toNumber(), 5_000_000);
+      assert.equal(basicTier.duration.toNumber(), 30 * DAY);
+      assert.equal(basicTier.features, 0b0001);
+      assert.isTrue(basicTier.active);
+
+      const proTier = await fetch(context, "TierConfig", tierPda(programId, 2));
+      assert.deepEqual(proTier.name, tierName("Pro"));
+      assert.equal(proTier.usdPrice.toNumber(), 20_000_000);
+      assert.equal(proTier.features, 0b1111);
+  });
+
+  it("Grows a Legacy Subscription to the Current Layout", async () => {
+      const payer = anchor.web3.Keypair.generate();
+      const user = anchor.web3.Keypair.generate().publicKey;
+      const otherUser = anchor.web3.Keypair.generate().publicKey;
+      const subscriptionPda = (owner: anchor.web3.PublicKey) =>
+        anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("subscription"), owner.toBuffer()],
+          programId
+        );
+      const [subscription, subscriptionBump] = subscriptionPda(user);
+      const [otherSubscription] = subscriptionPda(otherUser);
+      const expiresAt = 1_900_000_000;
+
+      // Subscription as first deployed: user, tier, started_at, expires_at,
+      // total_paid, bump
+      const legacySubscription = Buffer.alloc(66);
+      createHash("sha256").update("account:Subscription").digest().copy(legacySubscription, 0, 0, 8);
+      user.toBuffer().copy(legacySubscription, 8);
+      legacySubscription.writeUInt8(2, 40);
+      legacySubscription.writeBigInt64LE(BigInt(expiresAt - 30 * DAY), 41);
+      legacySubscription.writeBigInt64LE(BigInt(expiresAt), 49);
+      legacySubscription.writeBigUInt64LE(BigInt(20_000_000), 57);
+      legacySubscription.writeUInt8(subscriptionBump, 65);
+
+      // Program owned, but not a subscription
+      const notASubscription = Buffer.alloc(66);
+      createHash("sha256").update("account:TierConfig").digest().copy(notASubscription, 0, 0, 8);
+
+      const legacyRent = 1_350_240; // rent exemption for 66 bytes
+      const context = await startProgram(anchor.web3.Keypair.generate().publicKey, [
+        wallet(payer.publicKey),
+        { address: subscription, info: account(programId, legacySubscription, legacyRent) },
+        { address: otherSubscription, info: account(programId, notASubscription, legacyRent) },
+      ]);
+
+      const migrate = async (owner: anchor.web3.PublicKey, address: anchor.web3.PublicKey) => [
+        await program.methods
+            .migrateSubscription()
+            .accounts({
+                payer: payer.publicKey,
+                user: owner,
+                subscription: address,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .instruction(),
+      ];
+
+      await expectError(context, await migrate(otherUser, otherSubscription), [payer], "AccountDiscriminatorMismatch");
+      await expectSuccess(context, await migrate(user, subscription), [payer]);
+
+      // The payer tops the account up to the larger rent exemption
+      const rent = await context.banksClient.getRent();
+      const migrated = await context.banksClient.getAccount(subscription);
+      assert.equal(migrated.data.length, 953);
+      assert.equal(Number(migrated.lamports), Number(rent.minimumBalance(BigInt(953))));
+
+      const subAccount = await fetch(context, "Subscription", subscription);
+      assert.ok(subAccount.user.equals(user));
+      assert.equal(subAccount.tier, 2);
+      assert.equal(subAccount.expiresAt.toNumber(), expiresAt);
+      assert.equal(subAccount.totalPaid.toNumber(), 20_000_000);
+      assert.isNull(subAccount.referrer);
+      assert.equal(subAccount.paidPeriods.length, 0);
+  });
+});
@@ -1,52 +1,217 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { PredictionCopilot } from "../target/types/prediction_copilot";
+import { VerifyCaller } from "../target/types/verify_caller";
 import { assert } from "chai";
-import { TOKEN_PROGRAM_ID, createMint, createAccount, mintTo, getAccount } from "@solana/spl-token";
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createAccount,
+  createAssociatedTokenAccount,
+  getAssociatedTokenAddressSync,
+  mintTo,
+  getAccount,
+  transfer,
+} from "@solana/spl-token";
+import { createHash } from "crypto";
+import { expectError, tierName, tierPda } from "./admin-actions";
+
+const DAY = 24 * 60 * 60;
 
 describe("user-actions", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
 
   const program = anchor.workspace.PredictionCopilot as Program<PredictionCopilot>;
-  
+  const verifyCaller = anchor.workspace.VerifyCaller as Program<VerifyCaller>;
+  const payer = (provider.wallet as any).payer; // Access underlying keypair for spl-token funcs
+
   const [configPda] = anchor.web3.PublicKey.findProgramAddressSync(
     [Buffer.from("config")],
     program.programId
   );
-  
+
+  const [adminLogPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("admin_log")],
+    program.programId
+  );
+
+  const [renewalDelegatePda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("renewal_delegate")],
+    program.programId
+  );
+
+  const [revenueSplitPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("revenue_split")],
+    program.programId
+  );
+
   let usdcMint: anchor.web3.PublicKey;
+  let vaultPda: anchor.web3.PublicKey;
+  let vaultLedgerPda: anchor.web3.PublicKey;
   let userUsdc: anchor.web3.PublicKey;
-  let treasuryUsdc: anchor.web3.PublicKey;
   const user = anchor.web3.Keypair.generate();
-  
+
+  function subscriptionPda(wallet: anchor.web3.PublicKey) {
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("subscription"), wallet.toBuffer()],
+      program.programId
+    )[0];
+  }
+
+  function autoRenewalPda(wallet: anchor.web3.PublicKey) {
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("auto_renew"), wallet.toBuffer()],
+      program.programId
+    )[0];
+  }
+
+  function orgPda(owner: anchor.web3.PublicKey) {
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("org"), owner.toBuffer()],
+      program.programId
+    )[0];
+  }
+
+  function seatPda(org: anchor.web3.PublicKey, member: anchor.web3.PublicKey) {
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("seat"), org.toBuffer(), member.toBuffer()],
+      program.programId
+    )[0];
+  }
+
+  function referrerPda(wallet: anchor.web3.PublicKey) {
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("referrer"), wallet.toBuffer()],
+      program.programId
+    )[0];
+  }
+
+  async function balance(tokenAccount: anchor.web3.PublicKey) {
+    return Number((await getAccount(provider.connection, tokenAccount)).amount);
+  }
+
+  async function chainTime() {
+    const slot = await provider.connection.getSlot();
+    return await provider.connection.getBlockTime(slot);
+  }
+
+  // Creates a funded wallet holding `amount` of the payment mint
+  async function fundedUser(amount: number) {
+    const wallet = anchor.web3.Keypair.generate();
+    const sx = await provider.connection.requestAirdrop(wallet.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(sx);
+
+    const tokenAccount = await createAccount(provider.connection, payer, usdcMint, wallet.publicKey);
+    await mintTo(provider.connection, payer, usdcMint, tokenAccount, provider.wallet.publicKey, amount);
+    return { wallet, tokenAccount };
+  }
+
+  async function createTier(tierId: number, name: string, usdPrice: number, duration: number) {
+    const tierConfig = tierPda(program.programId, tierId);
+    if (await provider.connection.getAccountInfo(tierConfig)) {
+      return;
+    }
+    await program.methods
+      .createTier(tierId, tierName(name), new anchor.BN(usdPrice), new anchor.BN(duration), 0)
+      .accounts({
+          admin: provider.wallet.publicKey,
+          config: configPda,
+          tierConfig,
+          proposal: null,
+          adminLog: adminLogPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  async function setRefundPolicy(cancellationFeeBps: number, refundWindow: number) {
+    await program.methods
+      .setRefundPolicy(cancellationFeeBps, new anchor.BN(refundWindow))
+      .accounts({
+          admin: provider.wallet.publicKey,
+          config: configPda,
+          proposal: null,
+          adminLog: adminLogPda,
+      })
+      .rpc();
+  }
+
+  async function subscribe(
+    wallet: anchor.web3.Keypair,
+    tokenAccount: anchor.web3.PublicKey,
+    tier: number,
+    { promoCode = null, referrer = null }: {
+      promoCode?: anchor.web3.PublicKey | null;
+      referrer?: { wallet: anchor.web3.PublicKey; tokenAccount: anchor.web3.PublicKey } | null;
+    } = {}
+  ) {
+    const promoRedemption = promoCode
+      ? anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("promo_redemption"), promoCode.toBuffer(), wallet.publicKey.toBuffer()],
+          program.programId
+        )[0]
+      : null;
+    await program.methods
+      .subscribe(tier)
+      .accounts({
+          user: wallet.publicKey,
+          subscription: subscriptionPda(wallet.publicKey),
+          config: configPda,
+          tierConfig: tierPda(program.programId, tier),
+          userUsdc: tokenAccount,
+          vault: vaultPda,
+          vaultLedger: vaultLedgerPda,
+          usdcMint,
+          referrerProfile: referrer ? referrerPda(referrer.wallet) : null,
+          referrerUsdc: referrer ? referrer.tokenAccount : null,
+          promoCode,
+          promoRedemption,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+  }
+
+  async function cancel(wallet: anchor.web3.Keypair, tokenAccount: anchor.web3.PublicKey) {
+    await program.methods
+      .cancelSubscription()
+      .accounts({
+          user: wallet.publicKey,
+          subscription: subscriptionPda(wallet.publicKey),
+          config: configPda,
+          userUsdc: tokenAccount,
+          vault: vaultPda,
+          vaultLedger: vaultLedgerPda,
+          usdcMint,
+          autoRenewal: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([wallet])
+      .rpc();
+  }
+
   // Setup: Create a fresh user and mint USDC to them
   it("Setup User with USDC", async () => {
-      // Allow provider to pay for setup
-      const payer = (provider.wallet as any).payer; // Access underlying keypair for spl-token funcs
-      
-      // 1. Create Mint
-      usdcMint = await createMint(
-          provider.connection,
-          payer,
-          provider.wallet.publicKey,
-          null,
-          6
+      // The payment mint and its vault are set up by admin-actions
+      const configAccount = await program.account.programConfig.fetch(configPda);
+      usdcMint = configAccount.paymentMints[0].mint;
+      [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), usdcMint.toBuffer()],
+        program.programId
+      );
+      [vaultLedgerPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vault_ledger"), usdcMint.toBuffer()],
+        program.programId
       );
-      
-      // 2. Fund user with SOL for fees
+
+      // Fund user with SOL for fees
       const sx = await provider.connection.requestAirdrop(user.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL);
       await provider.connection.confirmTransaction(sx);
-      
-      // 3. Create User Token Account
-      userUsdc = await createAccount(
-          provider.connection,
-          payer,
-          usdcMint,
-          user.publicKey
-      );
-      
-      // 4. Mint 100 USDC to user
+
+      // Create User Token Account and mint 100 USDC to it
+      userUsdc = await createAccount(provider.connection, payer, usdcMint, user.publicKey);
       await mintTo(
           provider.connection,
           payer,
@@ -55,49 +220,600 @@ describe("user-actions", () => {
           provider.wallet.publicKey,
           100_000_000 // 100 USDC
       );
-      
-      // 5. Create Treasury Token Account (owned by treasury PDA or address in config)
-      // For this test, we need to know what the treasury address is in config.
-      // In a real test we'd fetch config.treasury.
-      const configAccount = await program.account.programConfig.fetch(configPda);
-      const treasuryPubkey = configAccount.treasury;
-      
-      treasuryUsdc = await createAccount(
+
+      console.log("User setup complete with 100 USDC");
+  });
+
+  it("Subscribes to Basic Tier", async () => {
+      const vaultBefore = await balance(vaultPda);
+
+      // Basic Tier = 1, 5 USDC
+      await subscribe(user, userUsdc, 1);
+
+      console.log("Subscribed to Basic");
+
+      const subAccount = await program.account.subscription.fetch(subscriptionPda(user.publicKey));
+      assert.equal(subAccount.tier, 1);
+      assert.ok(subAccount.expiresAt.toNumber() > Date.now()/1000);
+      assert.equal(subAccount.paidPeriods.length, 1);
+      assert.equal(subAccount.paidPeriods[0].amount.toNumber(), 5_000_000);
+      assert.equal(await balance(vaultPda) - vaultBefore, 5_000_000);
+  });
+
+  it("Upgrades to Pro with Prorated Credit", async () => {
+      const before = await balance(userUsdc);
+
+      await program.methods
+        .changeTier(2)
+        .accounts({
+            user: user.publicKey,
+            subscription: subscriptionPda(user.publicKey),
+            config: configPda,
+            oldTierConfig: tierPda(program.programId, 1),
+            newTierConfig: tierPda(program.programId, 2),
+            userUsdc,
+            vault: vaultPda,
+            vaultLedger: vaultLedgerPda,
+            usdcMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+
+      // Unused Basic time (~5 USDC) goes towards a full Pro period (20 USDC).
+      // A few seconds have passed, worth ~2 base units each.
+      const charged = before - await balance(userUsdc);
+      assert.isAtLeast(charged, 15_000_000);
+      assert.isAtMost(charged, 15_001_000);
+
+      const subAccount = await program.account.subscription.fetch(subscriptionPda(user.publicKey));
+      const now = await chainTime();
+      assert.equal(subAccount.tier, 2);
+      assert.approximately(subAccount.expiresAt.toNumber(), now + 30 * DAY, 60);
+
+      // Carried cash keeps its payment date, only the new charge is dated now
+      const [carriedPeriod, chargedPeriod] = subAccount.paidPeriods;
+      assert.equal(subAccount.paidPeriods.length, 2);
+      assert.approximately(carriedPeriod.amount.toNumber(), 5_000_000, 1_000);
+      assert.isAtMost(carriedPeriod.paidAt.toNumber(), chargedPeriod.paidAt.toNumber());
+      assert.equal(carriedPeriod.endsAt.toNumber(), chargedPeriod.startsAt.toNumber());
+      assert.equal(chargedPeriod.amount.toNumber(), charged);
+  });
+
+  it("Refunds Unused Time Minus the Cancellation Fee", async () => {
+      // 10% fee, no fee-free window
+      await setRefundPolicy(1_000, 0);
+      const before = await balance(userUsdc);
+
+      await cancel(user, userUsdc);
+
+      // ~20 USDC of unused Pro time, less 10%
+      const refund = await balance(userUsdc) - before;
+      assert.isAtLeast(refund, 17_999_000);
+      assert.isAtMost(refund, 18_000_000);
+
+      const subAccount = await program.account.subscription.fetch(subscriptionPda(user.publicKey));
+      assert.equal(subAccount.paidPeriods.length, 0);
+      assert.equal(subAccount.totalRefunded.toNumber(), refund);
+      assert.isAtMost(subAccount.expiresAt.toNumber(), await chainTime());
+  });
+
+  it("Refunds the Latest Payment in Full Inside the Refund Window", async () => {
+      await setRefundPolicy(1_000, 3600);
+      await subscribe(user, userUsdc, 1);
+      const before = await balance(userUsdc);
+
+      await cancel(user, userUsdc);
+
+      assert.equal(await balance(userUsdc) - before, 5_000_000);
+      await setRefundPolicy(0, 0);
+  });
+
+  it("Renews Through the Keeper Crank", async () => {
+      // Daily tier, so a fresh subscription is already inside the renewal window
+      await createTier(3, "Daily", 1_000_000, DAY);
+      await subscribe(user, userUsdc, 3);
+
+      await program.methods
+        .enableAutoRenew(new anchor.BN(5_000_000))
+        .accounts({
+            user: user.publicKey,
+            subscription: subscriptionPda(user.publicKey),
+            autoRenewal: autoRenewalPda(user.publicKey),
+            config: configPda,
+            userUsdc,
+            usdcMint,
+            renewalDelegate: renewalDelegatePda,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      const keeperUsdc = await createAccount(
           provider.connection,
           payer,
           usdcMint,
-          treasuryPubkey
+          provider.wallet.publicKey,
+          anchor.web3.Keypair.generate()
       );
-      
-      console.log("User setup complete with 100 USDC");
+      const expiresBefore = (await program.account.subscription.fetch(subscriptionPda(user.publicKey))).expiresAt;
+      const vaultBefore = await balance(vaultPda);
+
+      const renew = () =>
+        program.methods
+          .renewSubscription()
+          .accounts({
+              caller: provider.wallet.publicKey,
+              subscription: subscriptionPda(user.publicKey),
+              autoRenewal: autoRenewalPda(user.publicKey),
+              config: configPda,
+              tierConfig: tierPda(program.programId, 3),
+              userUsdc,
+              vault: vaultPda,
+              vaultLedger: vaultLedgerPda,
+              callerUsdc: keeperUsdc,
+              usdcMint,
+              renewalDelegate: renewalDelegatePda,
+              referrerProfile: null,
+              referrerUsdc: null,
+              tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+      await renew();
+
+      // 0.5% keeper tip, the vault keeps (and credits) the rest
+      assert.equal(await balance(keeperUsdc), 5_000);
+      assert.equal(await balance(vaultPda) - vaultBefore, 995_000);
+
+      const subAccount = await program.account.subscription.fetch(subscriptionPda(user.publicKey));
+      assert.equal(subAccount.expiresAt.toNumber(), expiresBefore.toNumber() + DAY);
+      assert.equal(subAccount.paidPeriods[subAccount.paidPeriods.length - 1].amount.toNumber(), 995_000);
+
+      // Still inside the window, but this due date has been renewed already
+      await expectError(renew(), "RenewalTooEarly");
   });
 
-  it("Subscribes to Basic Tier", async () => {
-      const [subscriptionPda] = anchor.web3.PublicKey.findProgramAddressSync(
-        [Buffer.from("subscription"), user.publicKey.toBuffer()],
+  it("Keeps the Locked Price and Passes On Price Cuts", async () => {
+      const setPolicy = (lockDuration: number, gracePeriod: number) =>
+        program.methods
+          .setPriceLockPolicy(new anchor.BN(lockDuration), new anchor.BN(gracePeriod))
+          .accounts({
+              admin: provider.wallet.publicKey,
+              config: configPda,
+              proposal: null,
+              adminLog: adminLogPda,
+          })
+          .rpc();
+
+      // Out-of-range policies would overflow the lock arithmetic
+      await expectError(setPolicy(11 * 365 * DAY, 0), "InvalidPriceLockPolicy");
+      await setPolicy(30 * DAY, DAY);
+
+      await createTier(4, "Locked", 10_000_000, 30 * DAY);
+      const { wallet, tokenAccount } = await fundedUser(50_000_000);
+      await subscribe(wallet, tokenAccount, 4);
+
+      let subAccount = await program.account.subscription.fetch(subscriptionPda(wallet.publicKey));
+      assert.equal(subAccount.lockedUsdPrice.toNumber(), 10_000_000);
+      assert.approximately(subAccount.priceLockExpiresAt.toNumber(), await chainTime() + 30 * DAY, 60);
+
+      // A price cut applies to locked subscribers straight away
+      await program.methods
+        .updateTier(4, new anchor.BN(8_000_000), null, null)
+        .accounts({
+            admin: provider.wallet.publicKey,
+            config: configPda,
+            tierConfig: tierPda(program.programId, 4),
+            proposal: null,
+            adminLog: adminLogPda,
+        })
+        .rpc();
+
+      const before = await balance(tokenAccount);
+      await subscribe(wallet, tokenAccount, 4);
+      assert.equal(before - await balance(tokenAccount), 8_000_000);
+
+      // Extending an active subscription keeps the original lock
+      subAccount = await program.account.subscription.fetch(subscriptionPda(wallet.publicKey));
+      assert.equal(subAccount.lockedUsdPrice.toNumber(), 10_000_000);
+
+      await setPolicy(0, 0);
+  });
+
+  it("Distributes Only the Non-Refundable Part of the Vault", async () => {
+      const team = anchor.web3.Keypair.generate().publicKey;
+      const dao = anchor.web3.Keypair.generate().publicKey;
+      const teamUsdc = await createAccount(provider.connection, payer, usdcMint, team);
+      const daoUsdc = await createAccount(provider.connection, payer, usdcMint, dao);
+
+      await program.methods
+        .setRevenueSplit([
+            { wallet: team, shareBps: 7_000 },
+            { wallet: dao, shareBps: 3_000 },
+        ])
+        .accounts({
+            admin: provider.wallet.publicKey,
+            config: configPda,
+            revenueSplit: revenueSplitPda,
+            proposal: null,
+            adminLog: adminLogPda,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      const distribute = (beneficiaryAccounts: anchor.web3.PublicKey[]) =>
+        program.methods
+          .distributeRevenue()
+          .accounts({
+              caller: provider.wallet.publicKey,
+              config: configPda,
+              revenueSplit: revenueSplitPda,
+              mint: usdcMint,
+              vault: vaultPda,
+              vaultLedger: vaultLedgerPda,
+              tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .remainingAccounts(beneficiaryAccounts.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false })))
+          .rpc();
+
+      // Accounts must follow the split order
+      await expectError(distribute([daoUsdc, teamUsdc]), "InvalidBeneficiaryAccount");
+
+      // Cancellation fees are kept, active subscriptions stay refundable
+      const { refundable } = await program.account.vaultLedger.fetch(vaultLedgerPda);
+      const total = await balance(vaultPda) - refundable.toNumber();
+      assert.isAbove(total, 0);
+
+      await distribute([teamUsdc, daoUsdc]);
+      assert.equal(await balance(teamUsdc), Math.floor(total * 7_000 / 10_000));
+      assert.equal(await balance(daoUsdc), Math.floor(total * 3_000 / 10_000));
+      assert.isAtLeast(await balance(vaultPda), refundable.toNumber());
+  });
+
+  it("Gifts a Subscription to Another Wallet", async () => {
+      const { wallet: gifter, tokenAccount: gifterUsdc } = await fundedUser(20_000_000);
+      const recipient = anchor.web3.Keypair.generate().publicKey;
+      const gift = (to: anchor.web3.PublicKey, giftId: number) => {
+        const id = new anchor.BN(giftId);
+        return program.methods
+          .giftSubscription(1, to, id, null)
+          .accounts({
+              payer: gifter.publicKey,
+              subscription: subscriptionPda(to),
+              giftReceipt: anchor.web3.PublicKey.findProgramAddressSync(
+                [Buffer.from("gift"), gifter.publicKey.toBuffer(), to.toBuffer(), id.toArrayLike(Buffer, 'le', 8)],
+                program.programId
+              )[0],
+              config: configPda,
+              tierConfig: tierPda(program.programId, 1),
+              payerUsdc: gifterUsdc,
+              vault: vaultPda,
+              usdcMint,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([gifter])
+          .rpc();
+      };
+
+      // Own subscriptions go through subscribe
+      await expectError(gift(gifter.publicKey, 0), "GiftToSelf");
+
+      await gift(recipient, 0);
+      assert.equal(await balance(gifterUsdc), 15_000_000);
+
+      const subAccount = await program.account.subscription.fetch(subscriptionPda(recipient));
+      assert.ok(subAccount.user.equals(recipient));
+      assert.equal(subAccount.tier, 1);
+      assert.approximately(subAccount.expiresAt.toNumber(), await chainTime() + 30 * DAY, 60);
+
+      // The gifter paid, so nothing is refundable to the recipient
+      assert.equal(subAccount.totalPaid.toNumber(), 0);
+      assert.equal(subAccount.paidPeriods.length, 0);
+  });
+
+  it("Starts a One-Time Trial for New Wallets Only", async () => {
+      const setTrialConfig = (trialsEnabled: boolean, trialTier: number, trialDuration: number) =>
+        program.methods
+          .setTrialConfig(trialsEnabled, trialTier, new anchor.BN(trialDuration))
+          .accounts({
+              admin: provider.wallet.publicKey,
+              config: configPda,
+              proposal: null,
+              adminLog: adminLogPda,
+          })
+          .rpc();
+      const startTrial = (wallet: anchor.web3.Keypair) =>
+        program.methods
+          .startTrial()
+          .accounts({
+              user: wallet.publicKey,
+              subscription: subscriptionPda(wallet.publicKey),
+              trialClaim: anchor.web3.PublicKey.findProgramAddressSync(
+                [Buffer.from("trial"), wallet.publicKey.toBuffer()],
+                program.programId
+              )[0],
+              config: configPda,
+              tierConfig: tierPda(program.programId, 2),
+              systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([wallet])
+          .rpc();
+
+      // 7 days of Pro
+      await setTrialConfig(true, 2, 7 * DAY);
+
+      const { wallet } = await fundedUser(0);
+      await startTrial(wallet);
+      const subAccount = await program.account.subscription.fetch(subscriptionPda(wallet.publicKey));
+      assert.equal(subAccount.tier, 2);
+      assert.ok(subAccount.isTrial);
+      assert.approximately(subAccount.expiresAt.toNumber(), await chainTime() + 7 * DAY, 60);
+
+      // Paying subscribers are not new users
+      const { wallet: subscriber, tokenAccount } = await fundedUser(5_000_000);
+      await subscribe(subscriber, tokenAccount, 1);
+      await expectError(startTrial(subscriber), "TrialUnavailable");
+
+      await setTrialConfig(false, 0, 0);
+  });
+
+  it("Applies a Promo Code Once per Wallet", async () => {
+      const codeHash = Array.from(createHash("sha256").update("LAUNCH50").digest());
+      const [promoCode] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("promo"), Buffer.from(codeHash)],
         program.programId
       );
-      
-      // Basic Tier = 1
+
+      // 50% off Basic only, one redemption per wallet
       await program.methods
-        .subscribe(1)
+        .createPromoCode(
+            codeHash,
+            0, // DISCOUNT_PERCENT
+            new anchor.BN(5_000),
+            new anchor.BN(1 << 1),
+            100,
+            1,
+            new anchor.BN(await chainTime() + 30 * DAY)
+        )
         .accounts({
-            user: user.publicKey,
-            subscription: subscriptionPda,
+            admin: provider.wallet.publicKey,
             config: configPda,
-            userUsdc: userUsdc,
-            treasuryUsdc: treasuryUsdc,
-            usdcMint: usdcMint,
+            promoCode,
+            proposal: null,
+            adminLog: adminLogPda,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      const { wallet, tokenAccount } = await fundedUser(20_000_000);
+      await subscribe(wallet, tokenAccount, 1, { promoCode });
+      assert.equal(await balance(tokenAccount), 17_500_000);
+
+      const promoAccount = await program.account.promoCode.fetch(promoCode);
+      assert.equal(promoAccount.redemptions, 1);
+
+      await expectError(subscribe(wallet, tokenAccount, 1, { promoCode }), "PromoCodeAlreadyRedeemed");
+  });
+
+  it("Pays the Bound Referrer on Every Payment", async () => {
+      const setCommission = (bps: number) =>
+        program.methods
+          .setReferralCommission(bps)
+          .accounts({
+              admin: provider.wallet.publicKey,
+              config: configPda,
+              proposal: null,
+              adminLog: adminLogPda,
+          })
+          .rpc();
+      await setCommission(1_000);
+
+      const { wallet: referrerWallet, tokenAccount: referrerUsdc } = await fundedUser(0);
+      await program.methods
+        .registerReferrer()
+        .accounts({
+            wallet: referrerWallet.publicKey,
+            referrerProfile: referrerPda(referrerWallet.publicKey),
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([referrerWallet])
+        .rpc();
+      const referrer = { wallet: referrerWallet.publicKey, tokenAccount: referrerUsdc };
+
+      // 10% of the 5 USDC Basic price goes to the referrer, the rest to the vault
+      const { wallet, tokenAccount } = await fundedUser(20_000_000);
+      const vaultBefore = await balance(vaultPda);
+      await subscribe(wallet, tokenAccount, 1, { referrer });
+      assert.equal(await balance(referrerUsdc), 500_000);
+      assert.equal(await balance(vaultPda) - vaultBefore, 4_500_000);
+
+      const subAccount = await program.account.subscription.fetch(subscriptionPda(wallet.publicKey));
+      assert.ok(subAccount.referrer.equals(referrerWallet.publicKey));
+      const profile = await program.account.referrerProfile.fetch(referrerPda(referrerWallet.publicKey));
+      assert.equal(profile.referralCount, 1);
+      assert.equal(profile.totalEarned.toNumber(), 500_000);
+
+      // Renewals cannot drop the bound referrer
+      await expectError(subscribe(wallet, tokenAccount, 1), "InvalidReferrer");
+      await subscribe(wallet, tokenAccount, 1, { referrer });
+      assert.equal(await balance(referrerUsdc), 1_000_000);
+
+      await setCommission(0);
+  });
+
+  it("Fills Org Seats up to the Purchased Count", async () => {
+      const { wallet: owner, tokenAccount: ownerUsdc } = await fundedUser(50_000_000);
+      const org = orgPda(owner.publicKey);
+
+      // Two Basic seats at 5 USDC each
+      await program.methods
+        .purchaseOrgSubscription(1, 2)
+        .accounts({
+            owner: owner.publicKey,
+            org,
+            config: configPda,
+            tierConfig: tierPda(program.programId, 1),
+            ownerUsdc,
+            vault: vaultPda,
+            usdcMint,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: anchor.web3.SystemProgram.programId,
         })
-        .signers([user]) 
+        .signers([owner])
         .rpc();
-        
-      console.log("Subscribed to Basic");
-      
-      const subAccount = await program.account.subscription.fetch(subscriptionPda);
+      assert.equal(await balance(ownerUsdc), 40_000_000);
+
+      const addMember = (member: anchor.web3.PublicKey) =>
+        program.methods
+          .addOrgMember(member)
+          .accounts({
+              owner: owner.publicKey,
+              org,
+              seat: seatPda(org, member),
+              systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([owner])
+          .rpc();
+      const [alice, bob, carol] = [0, 1, 2].map(() => anchor.web3.Keypair.generate().publicKey);
+
+      await addMember(alice);
+      await addMember(bob);
+      await expectError(addMember(carol), "NoSeatsAvailable");
+
+      // Removing a member frees the seat
+      await program.methods
+        .removeOrgMember()
+        .accounts({
+            owner: owner.publicKey,
+            org,
+            seat: seatPda(org, bob),
+        })
+        .signers([owner])
+        .rpc();
+      await addMember(carol);
+
+      const orgAccount = await program.account.orgSubscription.fetch(org);
+      assert.equal(orgAccount.seats, 2);
+      assert.equal(orgAccount.seatsUsed, 2);
+      assert.isNull(await provider.connection.getAccountInfo(seatPda(org, bob)));
+  });
+
+  it("Verifies Access Directly and Over CPI", async () => {
+      const { wallet, tokenAccount } = await fundedUser(20_000_000);
+      await subscribe(wallet, tokenAccount, 2);
+      const subscription = subscriptionPda(wallet.publicKey);
+      const verify = (tier: number) =>
+        program.methods
+          .verifySubscription()
+          .accounts({
+              user: wallet.publicKey,
+              tierConfig: tierPda(program.programId, tier),
+              subscription,
+              seat: null,
+              org: null,
+              pass: null,
+              passToken: null,
+          });
+
+      // Read through return data
+      const status = await verify(2).view();
+      const subAccount = await program.account.subscription.fetch(subscription);
+      assert.equal(status.tier, 2);
+      assert.equal(status.expiresAt.toNumber(), subAccount.expiresAt.toNumber());
+      assert.equal(status.features, 0b1111);
+
+      // A Pro subscription does not grant Basic
+      await expectError(verify(1).rpc(), "InvalidTier");
+
+      // The same check from another program
+      const gatedAction = (tier: number, requiredFeatures: number) =>
+        verifyCaller.methods
+          .gatedAction(requiredFeatures)
+          .accounts({
+              user: wallet.publicKey,
+              tierConfig: tierPda(program.programId, tier),
+              subscription,
+              predictionCopilotProgram: program.programId,
+          })
+          .signers([wallet])
+          .rpc();
+
+      await gatedAction(2, 0b0100);
+      await expectError(gatedAction(1, 0b0100), "InvalidTier");
+      await expectError(gatedAction(2, 0b10000), "MissingFeatures");
+  });
+
+  it("Mints a Transferable Pass and Redeems It on the Same Tier Only", async () => {
+      const { wallet: buyer, tokenAccount: buyerUsdc } = await fundedUser(25_000_000);
+      const passId = new anchor.BN(0);
+      const [passMint] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("pass_mint"), buyer.publicKey.toBuffer(), passId.toArrayLike(Buffer, 'le', 8)],
+        program.programId
+      );
+      const [pass] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("pass"), passMint.toBuffer()],
+        program.programId
+      );
+      const buyerPassToken = getAssociatedTokenAddressSync(passMint, buyer.publicKey);
+
+      // A Basic pass, priced like a Basic subscription
+      await program.methods
+        .subscribeAsPass(1, passId)
+        .accounts({
+            user: buyer.publicKey,
+            pass,
+            passMint,
+            userPassToken: buyerPassToken,
+            config: configPda,
+            tierConfig: tierPda(program.programId, 1),
+            userTokenAccount: buyerUsdc,
+            vault: vaultPda,
+            paymentMint: usdcMint,
+            userSubscription: subscriptionPda(buyer.publicKey),
+            referrerProfile: null,
+            referrerTokenAccount: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([buyer])
+        .rpc();
+      assert.equal(await balance(buyerUsdc), 20_000_000);
+      assert.equal(await balance(buyerPassToken), 1);
+      const passAccount = await program.account.subscriptionPass.fetch(pass);
+      assert.equal(passAccount.tier, 1);
+
+      const redeem = (holder: anchor.web3.Keypair, holderPassToken: anchor.web3.PublicKey) =>
+        program.methods
+          .redeemPass()
+          .accounts({
+              holder: holder.publicKey,
+              pass,
+              passMint,
+              holderPassToken,
+              subscription: subscriptionPda(holder.publicKey),
+              config: configPda,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([holder])
+          .rpc();
+
+      // The buyer is now on Pro, a Basic pass cannot stack onto it
+      await subscribe(buyer, buyerUsdc, 2);
+      await expectError(redeem(buyer, buyerPassToken), "PassTierMismatch");
+
+      // Sold on to a wallet without a subscription
+      const { wallet: holder } = await fundedUser(0);
+      const holderPassToken = await createAssociatedTokenAccount(provider.connection, payer, passMint, holder.publicKey);
+      await transfer(provider.connection, payer, buyerPassToken, holderPassToken, buyer, 1);
+      await redeem(holder, holderPassToken);
+
+      const subAccount = await program.account.subscription.fetch(subscriptionPda(holder.publicKey));
       assert.equal(subAccount.tier, 1);
-      assert.ok(subAccount.expiresAt.toNumber() > Date.now()/1000);
+      assert.equal(subAccount.expiresAt.toNumber(), passAccount.expiresAt.toNumber());
+      assert.isNull(await provider.connection.getAccountInfo(pass));
   });
 });